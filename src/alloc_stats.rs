@@ -0,0 +1,139 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        reset();
+        let data: Vec<u64> = vec![0; 1024];
+        let stats = snapshot();
+        drop(data);
+
+        assert!(stats.allocations >= 1);
+        assert!(stats.total_bytes >= 1024 * 8);
+        assert!(stats.peak_bytes >= 1024 * 8);
+    }
+
+    #[test]
+    fn overlapping_measurements_are_flagged() {
+        let (inner, outer) = measure(|| measure(|| vec![0u8; 64]).1);
+        assert!(inner.overlapped);
+        assert!(outer.overlapped);
+    }
+}
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static BASELINE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static TOTAL_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Measurements in progress, including those of solvers abandoned after a timeout
+static MEASURING: Mutex<usize> = Mutex::new(0);
+/// Set when a measurement starts while another is in progress; cleared when one starts alone
+static OVERLAPPED: AtomicBool = AtomicBool::new(false);
+
+/// Wraps the system allocator and keeps running totals of what has been requested.
+/// Installed as the global allocator in `main.rs` when the `alloc_stats` feature is on.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Allocation figures gathered since the last call to `reset`.
+/// `peak_bytes` is measured above whatever was already live at the reset.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AllocStats {
+    pub peak_bytes: usize,
+    pub total_bytes: usize,
+    pub allocations: usize,
+    /// Another measurement was running at some point, so these figures mix in its allocations
+    pub overlapped: bool
+}
+
+/// Starts a new measurement window, e.g. just before a solver is called.
+pub fn reset() {
+    let current = CURRENT_BYTES.load(Ordering::Relaxed);
+    BASELINE_BYTES.store(current, Ordering::Relaxed);
+    PEAK_BYTES.store(current, Ordering::Relaxed);
+    TOTAL_BYTES.store(0, Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+}
+
+pub fn snapshot() -> AllocStats {
+    let baseline = BASELINE_BYTES.load(Ordering::Relaxed);
+    AllocStats {
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline),
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        overlapped: OVERLAPPED.load(Ordering::Relaxed)
+    }
+}
+
+/// Ends a measurement when dropped, so a solver that panics doesn't leave one open forever
+struct Measuring;
+
+impl Drop for Measuring {
+    fn drop(&mut self) {
+        *MEASURING.lock().unwrap_or_else(| poisoned | poisoned.into_inner()) -= 1;
+    }
+}
+
+/// Calls `f`, counting the allocations made until it returns. The counters are shared by every thread,
+/// so the figures are flagged as `overlapped` if another measurement was in progress at any point,
+/// such as a solver still running after it timed out.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let mut measuring = MEASURING.lock().unwrap_or_else(| poisoned | poisoned.into_inner());
+    if *measuring == 0 {
+        OVERLAPPED.store(false, Ordering::Relaxed);
+        reset();
+    } else {
+        OVERLAPPED.store(true, Ordering::Relaxed);
+    }
+    *measuring += 1;
+    drop(measuring);
+    let guard = Measuring;
+    let result = f();
+    let stats = snapshot();
+    drop(guard);
+    (result, stats)
+}
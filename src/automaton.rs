@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let automaton = AhoCorasick::new(&["he", "she", "his", "hers"]);
+        let found: Vec<(usize, usize, usize)> = automaton
+            .find_overlapping(b"ushers")
+            .map(| m | (m.pattern, m.start, m.end))
+            .collect();
+        assert_eq!(found, vec![(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+    }
+
+    #[test]
+    fn leftmost_prefers_the_longest_at_the_earliest_start() {
+        let automaton = AhoCorasick::new(&["six", "sixteen", "x", "teen"]);
+        let found = automaton.leftmost(b"asixteen").unwrap();
+        assert_eq!((found.pattern, found.start, found.end), (1, 1, 8));
+        assert!(automaton.leftmost(b"abc").is_none());
+    }
+}
+
+/// One occurrence of a pattern, as a byte range of the text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// Index into the patterns the automaton was built from
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize
+}
+
+/// Aho-Corasick automaton over bytes, finding every occurrence of a set of patterns in one pass
+pub struct AhoCorasick {
+    /// Next state for each state and byte, with failure links already followed
+    next: Vec<[u32; 256]>,
+    /// Patterns ending at each state, including those reached through failure links
+    outputs: Vec<Vec<usize>>,
+    lengths: Vec<usize>
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        let mut next = vec![[0u32; 256]];
+        let mut outputs: Vec<Vec<usize>> = vec![Vec::new()];
+        // the trie, with 0 meaning no edge (the root is never a child)
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut state = 0;
+            for &byte in pattern.as_ref() {
+                if next[state][byte as usize] == 0 {
+                    next.push([0; 256]);
+                    outputs.push(Vec::new());
+                    next[state][byte as usize] = (next.len() - 1) as u32;
+                }
+                state = next[state][byte as usize] as usize;
+            }
+            outputs[state].push(index);
+        }
+
+        // breadth first, so each state's failure target is complete before its children need it
+        let mut fail = vec![0usize; next.len()];
+        let mut queue: VecDeque<usize> = next[0].iter().filter(| &&child | child != 0).map(| &child | child as usize).collect();
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+            let fallbacks = next[fail[state]];
+            for (slot, fallback) in next[state].iter_mut().zip(fallbacks) {
+                if *slot == 0 {
+                    *slot = fallback;
+                } else {
+                    fail[*slot as usize] = fallback as usize;
+                    queue.push_back(*slot as usize);
+                }
+            }
+        }
+
+        let lengths = patterns.iter().map(| pattern | pattern.as_ref().len()).collect();
+        Self { next, outputs, lengths }
+    }
+
+    /// Every match, in order of where they end; matches ending together come longest first
+    pub fn find_overlapping<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        text.iter().enumerate().flat_map(
+            move | (i, &byte) | {
+                state = self.next[state][byte as usize] as usize;
+                self.outputs[state].iter().map(
+                    move | &pattern | Match { pattern, start: i + 1 - self.lengths[pattern], end: i + 1 }
+                )
+            }
+        )
+    }
+
+    /// The match starting earliest, the longest one if several start there
+    pub fn leftmost(&self, text: &[u8]) -> Option<Match> {
+        let longest = self.lengths.iter().copied().max().unwrap_or(0);
+        let mut best: Option<Match> = None;
+        for found in self.find_overlapping(text) {
+            if let Some(best) = best {
+                // anything found from here on starts after the best so far
+                if found.end > best.start + longest {
+                    break;
+                }
+            }
+            let better = match best {
+                None => true,
+                Some(best) => (found.start, best.end) < (best.start, found.end)
+            };
+            if better {
+                best = Some(found);
+            }
+        }
+        best
+    }
+}
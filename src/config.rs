@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::options::PuzzleOptions;
+use crate::runner::DEFAULT_YEAR;
+use crate::trace::TraceLevel;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    const FILE: &str = "
+# runner defaults
+input_dir = \"inputs\"
+timeout_ms = 5000
+workers = 2
+
+[day5]
+timeout_ms = 60000   # the reverse search is slow
+
+[2023.day21]
+input = \"big/garden.txt\"
+trace = \"debug\"
+
+[day1]
+puzzle_vocabulary = \"english,roman\"
+puzzle_threads = 2
+";
+
+    #[test]
+    fn layers_defaults_file_day_and_cli() {
+        let config = Config::parse(FILE).unwrap();
+        let cli = Overrides { workers: Some(8), ..Overrides::default() };
+
+        let day5 = config.settings(2023, 5, &cli);
+        assert_eq!(day5.input_dir, PathBuf::from("inputs"));
+        assert_eq!(day5.timeout, Some(Duration::from_millis(60000)));
+        assert_eq!(day5.workers, 8);
+        assert_eq!(day5.output, OutputFormat::Text);
+        assert_eq!(day5.input, None);
+
+        let day21 = config.settings(2023, 21, &Overrides { trace: Some(TraceLevel::Info), ..Overrides::default() });
+        assert_eq!(day21.input, Some(PathBuf::from("big/garden.txt")));
+        assert_eq!(day21.timeout, Some(Duration::from_millis(5000)));
+        assert_eq!(day21.trace, TraceLevel::Info);
+
+        let mut cli = Overrides::default();
+        cli.set("puzzle_threads", "8").unwrap();
+        let day1 = config.settings(2023, 1, &cli);
+        assert_eq!(day1.puzzle.get("vocabulary").map(String::as_str), Some("english,roman"));
+        assert_eq!(day1.puzzle.get("threads").map(String::as_str), Some("8"));
+        assert!(day5.puzzle.is_empty());
+
+        let other_year = config.settings(2022, 21, &Overrides::default());
+        assert_eq!(other_year.input, None);
+        assert_eq!(other_year.trace, TraceLevel::Off);
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let error = Config::parse("workers = 2\n[day5]\ntimeout_ms = soon\n").unwrap_err();
+        assert_eq!(error.line, Some(3));
+        assert!(Config::parse("colour = \"red\"\n").unwrap_err().message.contains("colour"));
+        assert!(Config::parse("[week5]\n").is_err());
+        assert!(Config::parse("output = \"xml\"\n").is_err());
+    }
+
+    #[test]
+    fn only_the_default_file_may_be_missing() {
+        let missing = std::env::temp_dir().join("aoc2023_no_such_config.toml");
+        _ = fs::remove_file(&missing);
+        let error = Config::load(&missing).unwrap_err();
+        assert_eq!(error.line, None);
+        assert!(error.to_string().starts_with("can't read it: "));
+
+        let not_text = std::env::temp_dir().join("aoc2023_binary_config.toml");
+        fs::write(&not_text, [0xff, 0xfe, 0x00]).unwrap();
+        assert!(Config::load(&not_text).is_err());
+    }
+}
+
+/// How answers are printed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Answers followed by a timing table
+    Text,
+    /// One JSON object per problem
+    Json
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown output format {s:?}, expected text or json"))
+        }
+    }
+}
+
+/// Everything the runner needs to know for one problem
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// Inputs live in `<input_dir>/<year>/dayN.txt`
+    pub input_dir: PathBuf,
+    /// Use this file instead of the cached input
+    pub input: Option<PathBuf>,
+    pub output: OutputFormat,
+    pub timeout: Option<Duration>,
+    /// How many problems run at once
+    pub workers: usize,
+    pub trace: TraceLevel,
+    /// Every run is appended to this file
+    pub history: PathBuf,
+    /// Passed on to the solver, see `crate::options`
+    pub puzzle: PuzzleOptions
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            input_dir: PathBuf::from("./input"),
+            input: None,
+            output: OutputFormat::Text,
+            timeout: None,
+            workers: 1,
+            trace: TraceLevel::Off,
+            history: PathBuf::from(crate::history::DEFAULT_PATH),
+            puzzle: PuzzleOptions::new()
+        }
+    }
+}
+
+/// Settings given in one place (a section of the file, or the command line), each optional
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+    pub input_dir: Option<PathBuf>,
+    pub input: Option<PathBuf>,
+    pub output: Option<OutputFormat>,
+    pub timeout: Option<Duration>,
+    pub workers: Option<usize>,
+    pub trace: Option<TraceLevel>,
+    pub history: Option<PathBuf>,
+    /// Only the options given here; they are merged with those from other places
+    pub puzzle: PuzzleOptions
+}
+
+impl Overrides {
+    /// Sets `key` from its text value, which has already had any quotes removed.
+    /// Keys starting `puzzle_` are options for the solver itself.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, String> where T::Err: Display {
+            value.parse().map_err(| err | format!("bad value {value:?} for {key}: {err}"))
+        }
+        match key {
+            "input_dir" => self.input_dir = Some(PathBuf::from(value)),
+            "input" => self.input = Some(PathBuf::from(value)),
+            "output" => self.output = Some(parse(key, value)?),
+            "timeout_ms" => self.timeout = Some(Duration::from_millis(parse(key, value)?)),
+            "workers" => self.workers = Some(parse::<usize>(key, value)?.max(1)),
+            "trace" => self.trace = Some(parse(key, value)?),
+            "history" => self.history = Some(PathBuf::from(value)),
+            _ if key.starts_with("puzzle_") && key.len() > "puzzle_".len() => {
+                self.puzzle.insert(String::from(&key["puzzle_".len()..]), String::from(value));
+            },
+            _ => return Err(format!("unknown setting {key}"))
+        }
+        Ok(())
+    }
+
+    fn apply(&self, settings: &mut Settings) {
+        if let Some(input_dir) = &self.input_dir {
+            settings.input_dir = input_dir.clone();
+        }
+        if let Some(input) = &self.input {
+            settings.input = Some(input.clone());
+        }
+        settings.output = self.output.unwrap_or(settings.output);
+        settings.timeout = self.timeout.or(settings.timeout);
+        settings.workers = self.workers.unwrap_or(settings.workers);
+        settings.trace = self.trace.unwrap_or(settings.trace);
+        if let Some(history) = &self.history {
+            settings.history = history.clone();
+        }
+        settings.puzzle.extend(self.puzzle.clone());
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    /// The line at fault, or `None` if the file couldn't be read at all
+    pub line: Option<usize>,
+    pub message: String
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message)
+        }
+    }
+}
+
+/// A project config file: top level settings, then `[dayN]` or `[YYYY.dayN]` sections overriding them
+#[derive(Debug, Default)]
+pub struct Config {
+    global: Overrides,
+    days: HashMap<(u32, u32), Overrides>
+}
+
+impl Config {
+    pub const DEFAULT_PATH: &'static str = "./aoc.toml";
+
+    /// A missing file at the default path is the same as an empty one.
+    /// Any other file that can't be read, including one given with `--config` that doesn't exist, is an error.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && path == Path::new(Self::DEFAULT_PATH) => Ok(Self::default()),
+            Err(err) => Err(ConfigError { line: None, message: format!("can't read it: {err}") })
+        }
+    }
+
+    /// Reads the TOML subset used here: `[section]` headers, `key = value` pairs with string,
+    /// integer or bare values, and `#` comments
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        let mut section: Option<(u32, u32)> = None;
+
+        for (i, raw_line) in text.lines().enumerate() {
+            let error = | message: String | ConfigError { line: Some(i + 1), message };
+            let line = strip_comment(raw_line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(| l | l.strip_suffix(']')) {
+                let day = parse_section(name.trim()).ok_or_else(|| error(format!("unknown section [{name}]")))?;
+                section = Some(day);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| error(format!("expected key = value, got {line:?}")))?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(| v | v.strip_suffix('"'))
+                .unwrap_or(value);
+            let target = match section {
+                Some(day) => config.days.entry(day).or_default(),
+                None => &mut config.global
+            };
+            target.set(key.trim(), value).map_err(error)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Settings that apply to the whole run, such as the worker count
+    pub fn global_settings(&self, cli: &Overrides) -> Settings {
+        let mut settings = Settings::default();
+        self.global.apply(&mut settings);
+        cli.apply(&mut settings);
+        settings
+    }
+
+    /// Defaults, then the file's top level, then its section for this day, then the command line
+    pub fn settings(&self, year: u32, day: u32, cli: &Overrides) -> Settings {
+        let mut settings = Settings::default();
+        self.global.apply(&mut settings);
+        if let Some(day_overrides) = self.days.get(&(year, day)) {
+            day_overrides.apply(&mut settings);
+        }
+        cli.apply(&mut settings);
+        settings
+    }
+}
+
+/// `dayN` for the default year or `YYYY.dayN`
+fn parse_section(name: &str) -> Option<(u32, u32)> {
+    let (year, day) = match name.split_once('.') {
+        Some((year, day)) => (year.parse().ok()?, day),
+        None => (DEFAULT_YEAR, name)
+    };
+    Some((year, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// Drops a `#` comment, leaving any `#` inside a quoted string alone
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => ()
+        }
+    }
+    line
+}
@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::Path;
+
+use crate::runner;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    const PAGE: &str = "<main>\
+<article class=\"day-desc\"><h2>--- Day 6: Wait For It ---</h2>\
+<p>Some intro.</p><p>For example:</p>\
+<pre><code>Time:      7  15   30\nDistance:  9  40  200\n</code></pre>\
+<p>Then the first race &amp; the rest:</p>\
+<pre><code><em>x</em> &lt; 3\n</code></pre>\
+<p>Multiply these together to get <code><em>288</em></code>.</p></article>\
+<p>Your puzzle answer was <code>1234</code>.</p>\
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+<p>So you get <code><em>71503</em></code> in this example.</p></article>\
+</main>";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let page = PuzzlePage::parse(PAGE);
+
+        assert_eq!(page.examples, vec!["Time:      7  15   30\nDistance:  9  40  200\n"]);
+        assert_eq!(page.answers, vec!["288", "71503"]);
+    }
+
+    #[test]
+    fn decodes_and_strips() {
+        assert_eq!(text_content("<em>a</em> &lt;&amp;&gt; &quot;b&quot;"), "a <&> \"b\"");
+    }
+
+    #[test]
+    fn skeleton_uses_solver_wording() {
+        let dir = std::env::temp_dir().join("aoc2023_examples");
+        _ = fs::remove_dir_all(&dir);
+        let page = PuzzlePage::parse(PAGE);
+
+        let skeleton = write_examples(2023, 6, &page, &dir).unwrap();
+        let example = fs::read_to_string(dir.join("day6_test1.txt")).unwrap();
+
+        assert_eq!(example, page.examples[0]);
+        assert!(skeleton.contains("part1(\"./input/2023/day6_test1.txt\")"));
+        assert!(skeleton.contains("assert_eq!(result, \"The product is 288\");"));
+        assert!(skeleton.contains("assert_eq!(result, \"There are 71503 ways to win\");"));
+
+        // running again reuses the identical file instead of adding a second one
+        write_examples(2023, 6, &page, &dir).unwrap();
+        assert!(!dir.join("day6_test2.txt").exists());
+
+        // no solver for that year, so the answer is used as it is, and must come out as a valid literal
+        let page = PuzzlePage { examples: vec![String::from("1\n")], answers: vec![String::from("say \"hi\"\\\nbye")] };
+        let skeleton = write_examples(2022, 6, &page, &dir).unwrap();
+        assert!(skeleton.contains(r#"assert_eq!(result, "say \"hi\"\\\nbye");"#));
+    }
+}
+
+/// What can be recovered from a saved puzzle page
+#[derive(Debug)]
+pub struct PuzzlePage {
+    /// Example inputs, in page order
+    pub examples: Vec<String>,
+    /// Expected example answer for each part present on the page
+    pub answers: Vec<String>
+}
+
+impl PuzzlePage {
+    /// Each part is an `<article>`. Examples are the `<pre><code>` blocks introduced by a paragraph
+    /// mentioning an example, and a part's answer is the last `<code><em>` in its article.
+    pub fn parse(html: &str) -> Self {
+        let mut examples: Vec<String> = Vec::new();
+        let mut answers = Vec::new();
+
+        for article in between_all(html, "<article", "</article>") {
+            let mut previous_end = 0;
+            for (start, end) in spans(article, "<pre><code>", "</code></pre>") {
+                let lead_in = article[previous_end..start].rsplit("<p>").next().unwrap_or("").to_lowercase();
+                let example = text_content(&article[start + "<pre><code>".len()..end]);
+                if lead_in.contains("example") && !examples.contains(&example) {
+                    examples.push(example);
+                }
+                previous_end = end;
+            }
+
+            let emphasized = between_all(article, "<code><em>", "</em></code>");
+            if let Some(answer) = emphasized.iter().rev().find(| s | !s.contains('<')) {
+                answers.push(text_content(answer));
+            }
+        }
+
+        Self { examples, answers }
+    }
+}
+
+/// `examples <day> <puzzle.html> [--year Y]` writes the page's examples as test inputs and prints a test module
+pub fn examples_command(args: &[String]) {
+    let day: Option<u32> = args.first().and_then(| s | s.parse().ok());
+    let year: u32 = crate::flag_value(args, "--year").unwrap_or(runner::DEFAULT_YEAR);
+    let (Some(day), Some(html_path)) = (day, args.get(1)) else {
+        println!("Usage: examples <day> <puzzle.html> [--year Y]");
+        return;
+    };
+    let html = fs::read_to_string(html_path).expect("Could not read puzzle page");
+    let page = PuzzlePage::parse(&html);
+    if page.examples.is_empty() {
+        println!("No examples found in {html_path}");
+        return;
+    }
+
+    match write_examples(year, day, &page, Path::new(&format!("./input/{year}"))) {
+        Ok(skeleton) => print!("{skeleton}"),
+        Err(err) => println!("Could not write examples: {err}")
+    }
+}
+
+/// Saves each example as `dayN_testK.txt` in `dir` (normally `./input/<year>`), reusing an existing file with the same contents,
+/// and returns a test module for the parts whose answers were found.
+/// Part 1 is checked against the first example and part 2 against the last.
+pub fn write_examples(year: u32, day: u32, page: &PuzzlePage, dir: &Path) -> std::io::Result<String> {
+    fs::create_dir_all(dir)?;
+    let mut files = Vec::new();
+    for example in &page.examples {
+        let mut k = 1;
+        let name = loop {
+            let name = format!("day{day}_test{k}.txt");
+            match fs::read_to_string(dir.join(&name)) {
+                Ok(existing) if existing == *example => break name,
+                Ok(_) => k += 1,
+                Err(_) => {
+                    fs::write(dir.join(&name), example)?;
+                    break name;
+                }
+            }
+        };
+        files.push(name);
+    }
+
+    let mut skeleton = String::from("#[cfg(test)]\nmod testing {\n    use super::*;\n");
+    for (i, answer) in page.answers.iter().enumerate() {
+        let part = i as u32 + 1;
+        let file = if part == 1 { files.first() } else { files.last() };
+        let Some(file) = file else { continue };
+        let expected = expected_text(year, day, part, &dir.join(file), answer);
+        skeleton.push_str(&format!(
+            "\n    #[test]\n    fn part{part}_test1() {{\n        let result = part{part}(\"./input/{year}/{file}\");\n\n        assert_eq!(result, {expected:?});\n    }}\n"
+        ));
+    }
+    skeleton.push_str("}\n");
+    Ok(skeleton)
+}
+
+/// The solver's own sentence if it already contains the expected answer, otherwise just the answer
+fn expected_text(year: u32, day: u32, part: u32, path: &Path, answer: &str) -> String {
+    let solved = runner::solver(year, day, part).and_then(| solver | {
+        let output = std::panic::catch_unwind(|| solver(&path.to_string_lossy())).ok()?;
+        output.split_whitespace().any(| word | word == answer).then_some(output)
+    });
+    solved.unwrap_or_else(|| String::from(answer))
+}
+
+/// Byte ranges from every `open` to the first `close` after it
+fn spans(text: &str, open: &str, close: &str) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some(start) = text[from..].find(open).map(| i | from + i) {
+        let Some(end) = text[start..].find(close).map(| i | start + i) else { break };
+        found.push((start, end));
+        from = start + open.len();
+    }
+    found
+}
+
+fn between_all<'a>(text: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    spans(text, open, close)
+        .into_iter()
+        .map(| (start, end) | &text[start + open.len()..end])
+        .collect()
+}
+
+/// Drops markup tags and decodes the entities the puzzle pages use
+fn text_content(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => ()
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,830 @@
+use crate::rng::Rng;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::runner;
+    use std::fs;
+
+    fn write_temp(day: u32, seed: u64, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("aoc2023_generated_day{day}_{seed}.txt"));
+        fs::write(&path, contents).expect("Couldn't write generated input");
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=21 {
+            let first = generate(day, 3, 8).unwrap();
+            let second = generate(day, 3, 8).unwrap();
+            assert_eq!(first, second, "day {day}");
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in 1..=21 {
+            for seed in 0..3 {
+                let input = generate(day, seed, 8).unwrap();
+                let path = write_temp(day, seed, &input);
+                for part in 1..=2 {
+                    if let Some(solver) = runner::solver(2023, day, part) {
+                        solver(&path);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn day10_loop_length() {
+        let mut rng = Rng::new(11);
+        let cells = CellTree::random(&mut rng, 6, 6).outline();
+        let cells = scaled_loop(&mut rng, &cells, 1..4);
+        let input = day10(&mut Rng::new(11), 24);
+        let path = write_temp(10, 11, &input);
+        let expected = format!("The most distance point is {} steps", cells.len() / 2);
+        assert_eq!(crate::year2023::day10::part1(&path), expected);
+    }
+
+    #[test]
+    fn day20_counter_periods() {
+        let input = day20(&mut Rng::new(5), 6);
+        let path = write_temp(20, 5, &input);
+        let periods = counter_periods(&mut Rng::new(5), 6);
+        let expected = format!("Required {} button presses", periods.iter().product::<u64>());
+        assert_eq!(crate::year2023::day20::part2(&path), expected);
+    }
+
+    #[test]
+    fn day13_patterns_have_one_line_each() {
+        let mut rng = Rng::new(2);
+        for _ in 0..20 {
+            let pattern = mirror_pattern(&mut rng, 12);
+            assert_eq!(reflection_lines(&pattern, 0).len(), 1);
+            assert_eq!(reflection_lines(&pattern, 1).len(), 1);
+        }
+    }
+}
+
+/// Builds a random, valid input for `day` of 2023.
+/// `size` is roughly the number of lines (or the side of the grid) and is clamped where the
+/// solvers rely on properties of the real inputs.
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    let input = match day {
+        1 => day1(&mut rng, size),
+        2 => day2(&mut rng, size),
+        3 => day3(&mut rng, size),
+        4 => day4(&mut rng, size),
+        5 => day5(&mut rng, size),
+        6 => day6(&mut rng, size),
+        7 => day7(&mut rng, size),
+        8 => day8(&mut rng, size),
+        9 => day9(&mut rng, size),
+        10 => day10(&mut rng, size),
+        11 => day11(&mut rng, size),
+        12 => day12(&mut rng, size),
+        13 => day13(&mut rng, size),
+        14 => day14(&mut rng, size),
+        15 => day15(&mut rng, size),
+        16 => day16(&mut rng, size),
+        17 => day17(&mut rng, size),
+        18 => day18(&mut rng, size),
+        19 => day19(&mut rng, size),
+        20 => day20(&mut rng, size),
+        21 => day21(&mut rng, size),
+        _ => return None
+    };
+    Some(input)
+}
+
+const DIGIT_WORDS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+fn lowercase_word(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| (b'a' + rng.below(26) as u8) as char).collect()
+}
+
+fn grid_from(rng: &mut Rng, rows: usize, cols: usize, pick: impl Fn(&mut Rng) -> char) -> String {
+    let mut out = String::new();
+    for _ in 0..rows {
+        for _ in 0..cols {
+            out.push(pick(rng));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Lines of letters, digits and spelled-out digits. Every line has at least one numeral.
+fn day1(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut pieces: Vec<String> = Vec::new();
+        for _ in 0..rng.range(1..6) {
+            match rng.below(3) {
+                0 => {
+                    let len = rng.range(1..5);
+                    pieces.push(lowercase_word(rng, len));
+                },
+                1 => pieces.push(String::from(*rng.choose(&DIGIT_WORDS[1..]))),
+                _ => pieces.push(rng.range(1..10).to_string())
+            }
+        }
+        let position = rng.range(0..pieces.len() + 1);
+        pieces.insert(position, rng.range(1..10).to_string());
+        out.push_str(&pieces.concat());
+        out.push('\n');
+    }
+    out
+}
+
+fn day2(rng: &mut Rng, size: usize) -> String {
+    let colors = ["red", "green", "blue"];
+    let mut out = String::new();
+    for id in 1..=size {
+        let draws: Vec<String> = (0..rng.range(1..7))
+            .map(|_| {
+                let mut shown: Vec<&str> = colors.to_vec();
+                rng.shuffle(&mut shown);
+                shown.truncate(rng.range(1..4));
+                shown.iter()
+                    .map(|color| format!("{} {color}", rng.range(1..21)))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect();
+        out.push_str(&format!("Game {id}: {}\n", draws.join("; ")));
+    }
+    out
+}
+
+fn day3(rng: &mut Rng, size: usize) -> String {
+    let symbols = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+    let width = size.max(3);
+    let mut out = String::new();
+    for _ in 0..width {
+        let mut row = String::new();
+        while row.len() < width {
+            let remaining = width - row.len();
+            if remaining > 1 && rng.chance(0.15) {
+                let digits = rng.range(1..4).min(remaining - 1);
+                row.push_str(&rng.range(10usize.pow(digits as u32 - 1)..10usize.pow(digits as u32)).to_string());
+                row.push('.');
+            } else if rng.chance(0.08) {
+                row.push(*rng.choose(&symbols));
+            } else {
+                row.push('.');
+            }
+        }
+        out.push_str(&row);
+        out.push('\n');
+    }
+    out
+}
+
+/// Ten winning numbers and twenty-five held numbers per card, never winning copies past the last card
+fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for id in 1..=size {
+        let mut pool: Vec<usize> = (1..100).collect();
+        rng.shuffle(&mut pool);
+        let winning = &pool[..10];
+        let max_matches = (size - id).min(10);
+        let matches = rng.range(0..max_matches + 1).min(rng.range(0..max_matches + 1));
+        let mut held: Vec<usize> = winning[..matches].to_vec();
+        held.extend(&pool[10..35 - matches]);
+        rng.shuffle(&mut held);
+        let fmt_nums = |nums: &[usize]| nums.iter().map(|n| format!("{n:>2}")).collect::<Vec<String>>().join(" ");
+        out.push_str(&format!("Card {id:>3}: {} | {}\n", fmt_nums(winning), fmt_nums(&held)));
+    }
+    out
+}
+
+/// Each map is a permutation of `0..limit` made of shuffled contiguous segments
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let names = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+    let limit = 100 * size.max(2);
+    let mut out = String::from("seeds:");
+    for _ in 0..rng.range(1..5) {
+        let start = rng.range(0..limit - 1);
+        let len = rng.range(1..limit - start);
+        out.push_str(&format!(" {start} {len}"));
+    }
+    out.push_str("\n\n");
+
+    for pair in names.windows(2) {
+        out.push_str(&format!("{}-to-{} map:\n", pair[0], pair[1]));
+        let mut cuts: Vec<usize> = (0..rng.range(2..8)).map(|_| rng.range(1..limit)).collect();
+        cuts.push(0);
+        cuts.push(limit);
+        cuts.sort();
+        cuts.dedup();
+        let mut segments: Vec<(usize, usize)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        rng.shuffle(&mut segments);
+        let mut dest_start = 0;
+        let mut rules: Vec<String> = Vec::new();
+        for (src_start, len) in segments {
+            rules.push(format!("{dest_start} {src_start} {len}"));
+            dest_start += len;
+        }
+        rng.shuffle(&mut rules);
+        out.push_str(&rules.join("\n"));
+        out.push_str("\n\n");
+    }
+    out.truncate(out.len() - 1);
+    out
+}
+
+/// At most four races with two digit times, so that part 2's joined numbers fit in a `u64`
+fn day6(rng: &mut Rng, size: usize) -> String {
+    let races = size.clamp(1, 4);
+    let mut times = Vec::new();
+    let mut distances = Vec::new();
+    for _ in 0..races {
+        let time = rng.range(7..100);
+        let hold = rng.range(1..time);
+        let best = hold * (time - hold);
+        let distance = best - rng.range(1..best / 2 + 2).min(best - 1);
+        times.push(time);
+        distances.push(distance);
+    }
+    let mut out = String::from("Time:    ");
+    for time in &times {
+        out.push_str(&format!(" {time:>4}"));
+    }
+    out.push_str("\nDistance:");
+    for distance in &distances {
+        out.push_str(&format!(" {distance:>4}"));
+    }
+    out.push('\n');
+    out
+}
+
+fn day7(rng: &mut Rng, size: usize) -> String {
+    let cards: Vec<char> = "23456789TJQKA".chars().collect();
+    let mut out = String::new();
+    for _ in 0..size {
+        let hand: String = (0..5).map(|_| *rng.choose(&cards)).collect();
+        out.push_str(&format!("{hand} {}\n", rng.range(1..1001)));
+    }
+    out
+}
+
+/// Several ghost loops whose lengths are the instruction length times distinct primes,
+/// like the real input. `AAA` to `ZZZ` is the first loop.
+fn day8(rng: &mut Rng, size: usize) -> String {
+    let turns = size.clamp(1, 40);
+    let mut primes = vec![3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+    rng.shuffle(&mut primes);
+    let ghosts = rng.range(2..5);
+
+    let mut used: HashSet<String> = HashSet::from([String::from("AAA"), String::from("ZZZ")]);
+    let mut fresh_name = |rng: &mut Rng, last: char| -> String {
+        loop {
+            let mut name: String = (0..2).map(|_| (b'A' + rng.below(26) as u8) as char).collect();
+            name.push(last);
+            if used.insert(name.clone()) {
+                return name;
+            }
+        }
+    };
+    let middle_letters: Vec<char> = ('B'..='Y').collect();
+
+    let mut lines: Vec<String> = Vec::new();
+    for (ghost, prime) in primes.iter().take(ghosts).enumerate() {
+        let (start, end) = if ghost == 0 {
+            (String::from("AAA"), String::from("ZZZ"))
+        } else {
+            (fresh_name(rng, 'A'), fresh_name(rng, 'Z'))
+        };
+        let ring_len = turns * prime;
+        let mut ring: Vec<String> = (0..ring_len - 1)
+            .map(|_| {
+                let last = *rng.choose(&middle_letters);
+                fresh_name(rng, last)
+            })
+            .collect();
+        ring.push(end.clone());
+        lines.push(format!("{start} = ({}, {})", ring[0], ring[0]));
+        for i in 0..ring_len {
+            let next = &ring[(i + 1) % ring_len];
+            lines.push(format!("{} = ({next}, {next})", ring[i]));
+        }
+    }
+    rng.shuffle(&mut lines);
+
+    let instructions: String = (0..turns).map(|_| if rng.chance(0.5) {'L'} else {'R'}).collect();
+    format!("{instructions}\n\n{}\n", lines.join("\n"))
+}
+
+/// Sequences sampled from random low-degree polynomials
+fn day9(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let coefficients: Vec<i64> = (0..rng.range(1..6)).map(|_| rng.range(0..11) as i64 - 5).collect();
+        let values: Vec<String> = (0..21i64)
+            .map(|x| coefficients.iter().rev().fold(0i64, |total, c| total * x + c))
+            .map(|v| v.to_string())
+            .collect();
+        out.push_str(&values.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// A tree over the cells of a coarse grid. Its outline on a grid twice the size is
+/// always a single closed loop with no crossings.
+#[derive(Clone, Debug)]
+pub struct CellTree {
+    pub cells: Vec<(usize, usize)>,
+    pub edges: Vec<((usize, usize), (usize, usize))>
+}
+
+impl CellTree {
+    /// Grows a tree from a random cell until it covers most of a `rows` by `cols` grid
+    pub fn random(rng: &mut Rng, rows: usize, cols: usize) -> Self {
+        let rows = rows.max(1);
+        let cols = cols.max(1);
+        let mut in_tree = vec![false; rows * cols];
+        let mut cells: Vec<(usize, usize)> = Vec::new();
+        let mut edges: Vec<((usize, usize), (usize, usize))> = Vec::new();
+        let start = (rng.range(0..rows), rng.range(0..cols));
+        in_tree[start.0 * cols + start.1] = true;
+        cells.push(start);
+
+        let target = (rows * cols * rng.range(50..95) / 100).max(1);
+        let mut attempts = 0;
+        while cells.len() < target && attempts < 50 * rows * cols {
+            attempts += 1;
+            let (row, col) = *rng.choose(&cells);
+            let neighbor = match rng.below(4) {
+                0 if row > 0 => (row - 1, col),
+                1 if row + 1 < rows => (row + 1, col),
+                2 if col > 0 => (row, col - 1),
+                3 if col + 1 < cols => (row, col + 1),
+                _ => continue
+            };
+            if !in_tree[neighbor.0 * cols + neighbor.1] {
+                in_tree[neighbor.0 * cols + neighbor.1] = true;
+                cells.push(neighbor);
+                edges.push(((row, col).min(neighbor), (row, col).max(neighbor)));
+            }
+        }
+
+        Self { cells, edges }
+    }
+
+    /// Trees with one leaf removed, used when shrinking
+    #[cfg(test)]
+    pub fn without_leaves(&self) -> Vec<Self> {
+        if self.cells.len() < 2 {
+            return Vec::new();
+        }
+        self.cells
+            .iter()
+            .filter(|cell| self.edges.iter().filter(|(a, b)| a == *cell || b == *cell).count() == 1)
+            .map(|leaf| Self {
+                cells: self.cells.iter().copied().filter(|cell| cell != leaf).collect(),
+                edges: self.edges.iter().copied().filter(|(a, b)| a != leaf && b != leaf).collect()
+            })
+            .collect()
+    }
+
+    /// The outline of the tree on the doubled grid, in order around the loop
+    pub fn outline(&self) -> Vec<(usize, usize)> {
+        // each coarse cell starts as a ring of four fine cells; tree edges open the rings into each other
+        let mut edges: BTreeSet<((usize, usize), (usize, usize))> = BTreeSet::new();
+        for (row, col) in &self.cells {
+            let (r, c) = (2 * row, 2 * col);
+            edges.insert(((r, c), (r, c + 1)));
+            edges.insert(((r, c + 1), (r + 1, c + 1)));
+            edges.insert(((r + 1, c), (r + 1, c + 1)));
+            edges.insert(((r, c), (r + 1, c)));
+        }
+        for ((row, col), (other_row, _)) in &self.edges {
+            let (r, c) = (2 * row, 2 * col);
+            if *other_row == *row {
+                edges.remove(&((r, c + 1), (r + 1, c + 1)));
+                edges.remove(&((r, c + 2), (r + 1, c + 2)));
+                edges.insert(((r, c + 1), (r, c + 2)));
+                edges.insert(((r + 1, c + 1), (r + 1, c + 2)));
+            } else {
+                edges.remove(&((r + 1, c), (r + 1, c + 1)));
+                edges.remove(&((r + 2, c), (r + 2, c + 1)));
+                edges.insert(((r + 1, c), (r + 2, c)));
+                edges.insert(((r + 1, c + 1), (r + 2, c + 1)));
+            }
+        }
+
+        let mut adjacency: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        for (one, other) in &edges {
+            adjacency.entry(*one).or_default().push(*other);
+            adjacency.entry(*other).or_default().push(*one);
+        }
+
+        let first = (2 * self.cells[0].0, 2 * self.cells[0].1);
+        let mut cells = vec![first];
+        let mut prev = first;
+        let mut current = adjacency[&first][0];
+        while current != first {
+            cells.push(current);
+            let next = adjacency[&current].iter().copied().find(|cell| *cell != prev).unwrap();
+            prev = current;
+            current = next;
+        }
+        cells
+    }
+}
+
+/// A stretched pipe loop with junk pipes inside and around it and one `S` on the loop
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let coarse = (size / 4).max(2);
+    let cells = CellTree::random(rng, coarse, coarse).outline();
+    let cells = scaled_loop(rng, &cells, 1..4);
+    let side = cells.iter().map(|(r, c)| r.max(c)).max().unwrap() + 3;
+    let junk = ['|', '-', 'L', 'J', '7', 'F', '.', '.', '.'];
+    let mut grid: Vec<Vec<char>> = (0..side)
+        .map(|_| (0..side).map(|_| *rng.choose(&junk)).collect())
+        .collect();
+
+    let n = cells.len();
+    for i in 0..n {
+        let (row, col) = (cells[i].0 + 1, cells[i].1 + 1);
+        let mut dirs: Vec<char> = [cells[(i + n - 1) % n], cells[(i + 1) % n]]
+            .iter()
+            .map(|(r, c)| {
+                let (r, c) = (r + 1, c + 1);
+                if r < row {'N'} else if r > row {'S'} else if c < col {'W'} else {'E'}
+            })
+            .collect();
+        dirs.sort();
+        grid[row][col] = match (dirs[0], dirs[1]) {
+            ('N', 'S') => '|',
+            ('E', 'W') => '-',
+            ('E', 'N') => 'L',
+            ('N', 'W') => 'J',
+            ('S', 'W') => '7',
+            ('E', 'S') => 'F',
+            _ => panic!("Loop cells were not adjacent")
+        };
+    }
+
+    let (start_row, start_col) = *rng.choose(&cells);
+    let (start_row, start_col) = (start_row + 1, start_col + 1);
+    grid[start_row][start_col] = 'S';
+    // junk next to the start mustn't look like it connects to it
+    let on_loop: HashSet<(usize, usize)> = cells.iter().map(|(r, c)| (r + 1, c + 1)).collect();
+    for (r, c) in [(start_row - 1, start_col), (start_row + 1, start_col), (start_row, start_col - 1), (start_row, start_col + 1)] {
+        if !on_loop.contains(&(r, c)) {
+            grid[r][c] = '.';
+        }
+    }
+
+    grid.into_iter().map(|row| row.into_iter().collect::<String>() + "\n").collect()
+}
+
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(2);
+    let empty_rows: Vec<bool> = (0..side).map(|_| rng.chance(0.15)).collect();
+    let empty_cols: Vec<bool> = (0..side).map(|_| rng.chance(0.15)).collect();
+    let mut grid: Vec<Vec<char>> = (0..side)
+        .map(|r| (0..side).map(|c| if !empty_rows[r] && !empty_cols[c] && rng.chance(0.1) {'#'} else {'.'}).collect())
+        .collect();
+    if grid.iter().flatten().all(|ch| *ch == '.') {
+        grid[0][0] = '#';
+    }
+    grid.into_iter().map(|row| row.into_iter().collect::<String>() + "\n").collect()
+}
+
+/// Rows are generated fully known, the block list read off, and then some springs hidden
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = rng.range(4..17);
+        let mut springs: Vec<char> = (0..len).map(|_| if rng.chance(0.4) {'#'} else {'.'}).collect();
+        if !springs.contains(&'#') {
+            let index = rng.range(0..len);
+            springs[index] = '#';
+        }
+        let blocks: Vec<String> = springs
+            .split(|ch| *ch == '.')
+            .filter(|block| !block.is_empty())
+            .map(|block| block.len().to_string())
+            .collect();
+        let shown: String = springs.iter().map(|ch| if rng.chance(0.4) {'?'} else {*ch}).collect();
+        out.push_str(&format!("{shown} {}\n", blocks.join(",")));
+    }
+    out
+}
+
+/// Lines (row `k` meaning between rows `k-1` and `k`) about which the rows reflect with exactly `smudges` differences
+fn reflection_lines(pattern: &[Vec<bool>], smudges: usize) -> Vec<usize> {
+    let transposed: Vec<Vec<bool>> = (0..pattern[0].len())
+        .map(|c| pattern.iter().map(|row| row[c]).collect())
+        .collect();
+    let mut lines = Vec::new();
+    for (offset, grid) in [(100, pattern), (1, &transposed[..])] {
+        for k in 1..grid.len() {
+            let differences: usize = (0..k.min(grid.len() - k))
+                .map(|i| grid[k - 1 - i].iter().zip(grid[k + i].iter()).filter(|(a, b)| a != b).count())
+                .sum();
+            if differences == smudges {
+                lines.push(offset * k);
+            }
+        }
+    }
+    lines
+}
+
+/// A pattern with exactly one perfect reflection and exactly one reflection off by a single smudge
+fn mirror_pattern(rng: &mut Rng, size: usize) -> Vec<Vec<bool>> {
+    let max_side = size.clamp(5, 17) + 1;
+    loop {
+        let rows = rng.range(5..max_side);
+        let cols = rng.range(5..max_side);
+        let mut grid: Vec<Vec<bool>> = (0..rows).map(|_| (0..cols).map(|_| rng.chance(0.5)).collect()).collect();
+        let perfect = rng.range(1..rows / 2);
+        let smudged = rng.range(1..(rows - 2 * perfect) / 2 + 1);
+        for i in 0..perfect {
+            grid[2 * perfect - 1 - i] = grid[i].clone();
+        }
+        for i in 0..smudged {
+            grid[rows - smudged + i] = grid[rows - smudged - 1 - i].clone();
+        }
+        let row = rows - 1 - rng.range(0..smudged);
+        let col = rng.range(0..cols);
+        grid[row][col] = !grid[row][col];
+
+        if rng.chance(0.5) {
+            grid.reverse();
+        }
+        if rng.chance(0.5) {
+            grid = (0..cols).map(|c| grid.iter().map(|row| row[c]).collect()).collect();
+        }
+        if reflection_lines(&grid, 0).len() == 1 && reflection_lines(&grid, 1).len() == 1 {
+            return grid;
+        }
+    }
+}
+
+/// The day 13 reader expects every pattern, including the last, to be followed by a blank line
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.max(1).div_ceil(10) + 1 {
+        for row in mirror_pattern(rng, size) {
+            out.extend(row.iter().map(|rock| if *rock {'#'} else {'.'}));
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn day14(rng: &mut Rng, size: usize) -> String {
+    grid_from(rng, size, size, |rng| match rng.below(10) {
+        0 => '#',
+        1 | 2 => 'O',
+        _ => '.'
+    })
+}
+
+fn day15(rng: &mut Rng, size: usize) -> String {
+    let labels: Vec<String> = (0..size / 2 + 1).map(|_| {
+        let len = rng.range(2..7);
+        lowercase_word(rng, len)
+    }).collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(0.3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.range(1..10))
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+fn day16(rng: &mut Rng, size: usize) -> String {
+    grid_from(rng, size, size, |rng| if rng.chance(0.8) {'.'} else {*rng.choose(&['/', '\\', '|', '-'])})
+}
+
+fn day17(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(5);
+    grid_from(rng, side, side, |rng| char::from_digit(rng.range(1..10) as u32, 10).unwrap())
+}
+
+/// Random strictly increasing positions for `0..=extent`, with gaps in `steps`.
+/// Mapping rows and columns through these keeps a loop from crossing itself.
+fn monotone_positions(rng: &mut Rng, extent: usize, steps: Range<usize>) -> Vec<usize> {
+    let mut positions = vec![0usize];
+    for _ in 0..extent {
+        positions.push(positions.last().unwrap() + rng.range(steps.clone()));
+    }
+    positions
+}
+
+/// The loop stretched by random row heights and column widths, as `(direction, distance)` runs
+fn scaled_runs(rng: &mut Rng, cells: &[(usize, usize)], steps: Range<usize>) -> Vec<(char, usize)> {
+    let extent = cells.iter().map(|(r, c)| r.max(c)).max().unwrap() + 1;
+    let row_positions = monotone_positions(rng, extent, steps.clone());
+    let col_positions = monotone_positions(rng, extent, steps);
+    loop_runs(cells, &row_positions, &col_positions)
+}
+
+/// Walks the loop with each row and column moved to the given positions, merging straight runs
+pub fn loop_runs(cells: &[(usize, usize)], row_positions: &[usize], col_positions: &[usize]) -> Vec<(char, usize)> {
+    let mut runs: Vec<(char, usize)> = Vec::new();
+    for i in 0..cells.len() {
+        let (row, col) = cells[i];
+        let (next_row, next_col) = cells[(i + 1) % cells.len()];
+        let (dir, dist) = if next_row < row {
+            ('U', row_positions[row] - row_positions[next_row])
+        } else if next_row > row {
+            ('D', row_positions[next_row] - row_positions[row])
+        } else if next_col < col {
+            ('L', col_positions[col] - col_positions[next_col])
+        } else {
+            ('R', col_positions[next_col] - col_positions[col])
+        };
+        match runs.last_mut() {
+            Some((last_dir, last_dist)) if *last_dir == dir => *last_dist += dist,
+            _ => runs.push((dir, dist))
+        }
+    }
+    // the loop may start part way along a straight run
+    if runs.len() > 1 && runs[0].0 == runs.last().unwrap().0 {
+        let (_, dist) = runs.pop().unwrap();
+        runs[0].1 += dist;
+    }
+    runs
+}
+
+/// The same loop stretched onto a bigger grid, with every cell along the way
+fn scaled_loop(rng: &mut Rng, cells: &[(usize, usize)], steps: Range<usize>) -> Vec<(usize, usize)> {
+    let runs = scaled_runs(rng, cells, steps);
+    let (mut row, mut col) = (0isize, 0isize);
+    let mut path: Vec<(isize, isize)> = Vec::new();
+    for (dir, dist) in runs {
+        for _ in 0..dist {
+            path.push((row, col));
+            match dir {
+                'U' => row -= 1,
+                'D' => row += 1,
+                'L' => col -= 1,
+                _ => col += 1
+            }
+        }
+    }
+    let min_row = path.iter().map(|(r, _)| *r).min().unwrap();
+    let min_col = path.iter().map(|(_, c)| *c).min().unwrap();
+    path.into_iter().map(|(r, c)| ((r - min_row) as usize, (c - min_col) as usize)).collect()
+}
+
+/// The colour codes describe the same outline as the plain instructions, at a much larger scale.
+/// Gaps of at least 2 keep the trench from running alongside itself, as in the real input.
+fn day18(rng: &mut Rng, size: usize) -> String {
+    let coarse = (size / 2).max(2);
+    let cells = CellTree::random(rng, coarse, coarse).outline();
+    let small = scaled_runs(rng, &cells, 2..6);
+    let large = scaled_runs(rng, &cells, 2..200_000 / (2 * coarse));
+    let mut out = String::new();
+    for ((dir, dist), (hex_dir, hex_dist)) in small.iter().zip(large.iter()) {
+        let dir_digit = match hex_dir {
+            'R' => 0,
+            'D' => 1,
+            'L' => 2,
+            _ => 3
+        };
+        out.push_str(&format!("{dir} {dist} (#{hex_dist:05x}{dir_digit})\n"));
+    }
+    out
+}
+
+/// A tree of workflows rooted at `in`, so every part ends at `A` or `R`
+fn day19(rng: &mut Rng, size: usize) -> String {
+    let mut names: HashSet<String> = HashSet::from([String::from("in")]);
+    let mut pending = vec![String::from("in")];
+    let mut workflows: Vec<String> = Vec::new();
+    while let Some(name) = pending.pop() {
+        let mut destination = |rng: &mut Rng, pending: &mut Vec<String>| -> String {
+            if names.len() < size && rng.chance(0.6) {
+                loop {
+                    let len = rng.range(2..4);
+                    let new_name = lowercase_word(rng, len);
+                    if names.insert(new_name.clone()) {
+                        pending.push(new_name.clone());
+                        return new_name;
+                    }
+                }
+            } else {
+                String::from(if rng.chance(0.5) {"A"} else {"R"})
+            }
+        };
+        let mut rules: Vec<String> = (0..rng.range(1..4))
+            .map(|_| {
+                let category = rng.choose(&['x', 'm', 'a', 's']);
+                let op = if rng.chance(0.5) {'<'} else {'>'};
+                let value = rng.range(1..4001);
+                format!("{category}{op}{value}:{}", destination(rng, &mut pending))
+            })
+            .collect();
+        rules.push(destination(rng, &mut pending));
+        workflows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    rng.shuffle(&mut workflows);
+
+    let mut out = workflows.join("\n");
+    out.push_str("\n\n");
+    for _ in 0..size {
+        let ratings: Vec<String> = ['x', 'm', 'a', 's']
+            .iter()
+            .map(|category| format!("{category}={}", rng.range(1..4001)))
+            .collect();
+        out.push_str(&format!("{{{}}}\n", ratings.join(",")));
+    }
+    out
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
+
+/// Four distinct primes that each need `bits` bits
+fn counter_periods(rng: &mut Rng, bits: usize) -> Vec<u64> {
+    let low = 1u64 << (bits - 1);
+    let mut candidates: Vec<u64> = (low..2 * low).filter(|n| is_prime(*n)).collect();
+    rng.shuffle(&mut candidates);
+    candidates.truncate(4);
+    candidates
+}
+
+/// Four flip-flop counters, each resetting at a prime period, feeding the conjunctions
+/// that `day20::part2` watches. `size` sets the number of bits per counter.
+fn day20(rng: &mut Rng, size: usize) -> String {
+    let bits = size.clamp(5, 20);
+    let periods = counter_periods(rng, bits);
+    let reserved = ["gc", "sz", "xf", "cm", "zr", "rx"];
+    let mut used: HashSet<String> = reserved.iter().map(|s| String::from(*s)).collect();
+    let mut fresh_name = |rng: &mut Rng| loop {
+        let name = lowercase_word(rng, 2);
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut firsts: Vec<String> = Vec::new();
+    for (period, inverter) in periods.iter().zip(["gc", "sz", "xf", "cm"]) {
+        let flip_flops: Vec<String> = (0..bits).map(|_| fresh_name(rng)).collect();
+        let hub = fresh_name(rng);
+        let mut hub_outputs = vec![String::from(inverter)];
+        for (bit, name) in flip_flops.iter().enumerate() {
+            let mut outputs: Vec<String> = Vec::new();
+            if let Some(next) = flip_flops.get(bit + 1) {
+                outputs.push(next.clone());
+            }
+            if (period >> bit) & 1 == 1 {
+                outputs.push(hub.clone());
+            }
+            if bit == 0 || (period >> bit) & 1 == 0 {
+                hub_outputs.push(name.clone());
+            }
+            rng.shuffle(&mut outputs);
+            lines.push(format!("%{name} -> {}", outputs.join(", ")));
+        }
+        rng.shuffle(&mut hub_outputs);
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&{inverter} -> zr"));
+        firsts.push(flip_flops[0].clone());
+    }
+    lines.push(String::from("&zr -> rx"));
+    lines.push(format!("broadcaster -> {}", firsts.join(", ")));
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+/// An odd-sided garden with `S` in the middle and a clear row and column through it
+fn day21(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(3) | 1;
+    let middle = side / 2;
+    let mut out = String::new();
+    for row in 0..side {
+        for col in 0..side {
+            if row == middle && col == middle {
+                out.push('S');
+            } else if row == middle || col == middle || !rng.chance(0.15) {
+                out.push('.');
+            } else {
+                out.push('#');
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
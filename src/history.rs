@@ -0,0 +1,316 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::options::PuzzleOptions;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    fn run(time: u64, answer: &str, millis: u64) -> Entry {
+        Entry {
+            kind: Kind::Run,
+            time,
+            commit: String::from("abc1234"),
+            year: 2023,
+            day: 12,
+            part: 2,
+            input: String::from("./input/2023/day12.txt"),
+            options: String::new(),
+            duration: Some(Duration::from_millis(millis)),
+            answer: String::from(answer)
+        }
+    }
+
+    #[test]
+    fn entries_survive_a_round_trip() {
+        let mut entry = run(1_700_000_000, "The sum is 21\twith a tab\nand a line", 12);
+        assert_eq!(Entry::parse(&entry.to_line()), Some(entry.clone()));
+        entry.kind = Kind::Accept;
+        entry.duration = None;
+        assert_eq!(Entry::parse(&entry.to_line()), Some(entry.clone()));
+        entry.options = options_key(&PuzzleOptions::from([(String::from("report"), String::from("csv"))]));
+        assert_eq!(Entry::parse(&entry.to_line()), Some(entry));
+        assert_eq!(Entry::parse("run\tnot enough fields"), None);
+        // lines written before options were recorded
+        let old = Entry::parse("run\t1\tabc1234\t2023\t12\t2\t./input/2023/day12.txt\t12000\tThe sum is 21").unwrap();
+        assert_eq!(old.options, "");
+    }
+
+    #[test]
+    fn flags_answers_that_differ_from_the_accepted_one() {
+        let mut accept = run(4, "The sum is 30", 0);
+        accept.kind = Kind::Accept;
+        accept.duration = None;
+        let entries = vec![
+            run(1, "The sum is 21", 10),
+            run(2, "The sum is 20", 9),
+            run(3, "The sum is 21", 8),
+            accept,
+            run(5, "The sum is 30", 7),
+            run(6, "The sum is 21", 6)
+        ];
+        let flags: Vec<bool> = annotate(&entries).into_iter().map(| (_, changed) | changed).collect();
+        assert_eq!(flags, vec![false, true, false, false, false, true]);
+        assert_eq!(accepted_answer(&entries, "./input/2023/day12.txt", "").as_deref(), Some("The sum is 30"));
+    }
+
+    #[test]
+    fn runs_with_other_options_are_kept_apart() {
+        let mut report = run(2, "game,minimum\n1,4 red", 10);
+        report.options = String::from("report=csv");
+        let entries = vec![run(1, "The sum is 8", 10), report, run(3, "The sum is 8", 10)];
+        let flags: Vec<bool> = annotate(&entries).into_iter().map(| (_, changed) | changed).collect();
+        assert_eq!(flags, vec![false, false, false]);
+        assert_eq!(accepted_answer(&entries, "./input/2023/day12.txt", "report=csv").as_deref(), Some("game,minimum\n1,4 red"));
+        let table = history_table(&entries);
+        assert!(table.contains("input ./input/2023/day12.txt with report=csv\n"));
+        assert!(table.contains("game,minimum\\n1,4 red\n"));
+    }
+
+    #[test]
+    fn unix_time_to_date() {
+        assert_eq!(format_time(0), "1970-01-01 00:00:00");
+        assert_eq!(format_time(1_701_388_800), "2023-12-01 00:00:00");
+        assert_eq!(format_time(951_827_696), "2000-02-29 12:34:56");
+    }
+}
+
+/// Where runs are logged unless the config says otherwise
+pub const DEFAULT_PATH: &str = "./history.tsv";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A solver run
+    Run,
+    /// The answer the runs after it are checked against
+    Accept
+}
+
+/// One line of the history file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub kind: Kind,
+    /// Seconds since the Unix epoch
+    pub time: u64,
+    /// Short hash of the checked out commit, `+` if there were uncommitted changes, `-` outside git
+    pub commit: String,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    /// The puzzle options the run was given, from `options_key`, since some of them change what the answer is
+    pub options: String,
+    pub duration: Option<Duration>,
+    pub answer: String
+}
+
+impl Entry {
+    /// Tab separated, with tabs, newlines and backslashes in the answer escaped
+    fn to_line(&self) -> String {
+        let kind = match self.kind {
+            Kind::Run => "run",
+            Kind::Accept => "accept"
+        };
+        let micros = self.duration.map_or(String::from("-"), | duration | duration.as_micros().to_string());
+        format!(
+            "{kind}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{micros}\t{}",
+            self.time, self.commit, self.year, self.day, self.part, escape(&self.input), escape(&self.options), escape(&self.answer)
+        )
+    }
+
+    /// Runs are only compared with others on the same input given the same options
+    fn key(&self) -> (&str, &str) {
+        (&self.input, &self.options)
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        // older files have no options column
+        let [kind, time, commit, year, day, part, input, options, micros, answer] = match fields[..] {
+            [kind, time, commit, year, day, part, input, micros, answer] => [kind, time, commit, year, day, part, input, "", micros, answer],
+            [kind, time, commit, year, day, part, input, options, micros, answer] => [kind, time, commit, year, day, part, input, options, micros, answer],
+            _ => return None
+        };
+        let kind = match kind {
+            "run" => Kind::Run,
+            "accept" => Kind::Accept,
+            _ => return None
+        };
+        let duration = match micros {
+            "-" => None,
+            micros => Some(Duration::from_micros(micros.parse().ok()?))
+        };
+        Some(Self {
+            kind,
+            time: time.parse().ok()?,
+            commit: String::from(commit),
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            part: part.parse().ok()?,
+            input: unescape(input),
+            options: unescape(options),
+            duration,
+            answer: unescape(answer)
+        })
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\')
+        }
+    }
+    out
+}
+
+/// Puzzle options as `name=value` pairs separated by spaces, empty when there are none
+pub fn options_key(options: &PuzzleOptions) -> String {
+    options.iter().map(| (name, value) | format!("{name}={value}")).collect::<Vec<String>>().join(" ")
+}
+
+/// Every entry in the file, skipping lines that can't be read. A missing file has no entries.
+pub fn read(path: &Path) -> Vec<Entry> {
+    fs::read_to_string(path)
+        .map(| text | text.lines().filter_map(Entry::parse).collect())
+        .unwrap_or_default()
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&entry.to_line());
+        text.push('\n');
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())
+}
+
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, | since | since.as_secs())
+}
+
+/// Short hash of HEAD with a `+` for a dirty tree, or `-` if git isn't available
+pub fn current_commit() -> String {
+    let git = | args: &[&str] | {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(| output | output.status.success())
+            .map(| output | String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) if git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(| s | !s.is_empty()) => hash + "+",
+        Some(hash) => hash,
+        None => String::from("-")
+    }
+}
+
+/// Pairs each entry with whether its answer differs from the accepted answer for its input and options at that point.
+/// Until an answer is accepted explicitly, the first run on an input counts as accepted.
+pub fn annotate(entries: &[Entry]) -> Vec<(&Entry, bool)> {
+    let mut accepted: Vec<((&str, &str), &str)> = Vec::new();
+    entries
+        .iter()
+        .map(
+            | entry | {
+                let current = accepted.iter_mut().find(| (key, _) | *key == entry.key());
+                let changed = match (entry.kind, current) {
+                    (Kind::Run, Some((_, answer))) => *answer != entry.answer,
+                    (Kind::Accept, Some((_, answer))) => {
+                        *answer = &entry.answer;
+                        false
+                    },
+                    (_, None) => {
+                        accepted.push((entry.key(), &entry.answer));
+                        false
+                    }
+                };
+                (entry, changed)
+            }
+        )
+        .collect()
+}
+
+/// The answer new runs on `input` with `options` are compared with, taking only the entries for one problem
+pub fn accepted_answer(entries: &[Entry], input: &str, options: &str) -> Option<String> {
+    let on_input: Vec<&Entry> = entries.iter().filter(| entry | entry.key() == (input, options)).collect();
+    on_input
+        .iter()
+        .rev()
+        .find(| entry | entry.kind == Kind::Accept)
+        .or(on_input.first())
+        .map(| entry | entry.answer.clone())
+}
+
+/// `YYYY-MM-DD hh:mm:ss` in UTC
+pub fn format_time(time: u64) -> String {
+    let (days, seconds) = (time / 86400, time % 86400);
+    // civil date from days since 1970-01-01, counting in 400 year eras that start on March 1st
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// The history of one problem as a table, oldest first, marking runs whose answer changed.
+/// Answers over several lines are kept to one, with the line breaks escaped.
+pub fn history_table(entries: &[Entry]) -> String {
+    let mut out = format!("{:<21}{:<10}{:>12}{:>12}  {:<9}{}\n", "when (UTC)", "commit", "time (ms)", "change", "", "answer");
+    let mut previous: Vec<((&str, &str), Duration)> = Vec::new();
+    let mut current = None;
+    for (entry, changed) in annotate(entries) {
+        if current != Some(entry.key()) {
+            current = Some(entry.key());
+            match entry.options.as_str() {
+                "" => out.push_str(&format!("input {}\n", entry.input)),
+                options => out.push_str(&format!("input {} with {options}\n", entry.input))
+            }
+        }
+        let when = format_time(entry.time);
+        let answer = escape(&entry.answer);
+        let Some(duration) = entry.duration else {
+            out.push_str(&format!("{when:<21}{:<10}{:>12}{:>12}  {:<9}{answer}\n", entry.commit, "", "", "accepted"));
+            continue;
+        };
+        let millis = duration.as_secs_f64() * 1000.0;
+        let change = match previous.iter_mut().find(| (key, _) | *key == entry.key()) {
+            Some((_, last)) => {
+                let change = format!("{:+.3}", millis - last.as_secs_f64() * 1000.0);
+                *last = duration;
+                change
+            },
+            None => {
+                previous.push((entry.key(), duration));
+                String::new()
+            }
+        };
+        let flag = if changed { "CHANGED" } else { "" };
+        out.push_str(&format!("{when:<21}{:<10}{millis:>12.3}{change:>12}  {flag:<9}{answer}\n", entry.commit));
+    }
+    out
+}
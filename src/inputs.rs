@@ -0,0 +1,296 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2023_inputs_{name}"));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    struct CountingFetcher {
+        calls: Rc<Cell<u32>>
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn fetch(&self, day: u32) -> Result<String, FetchError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(format!("input for day {day}\n"))
+        }
+    }
+
+    /// Serves one canned response and hands back the request it received
+    fn stub_server(response: &'static str) -> (u16, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn fetches_only_when_missing() {
+        let dir = temp_dir("cache");
+        let calls = Rc::new(Cell::new(0));
+        let fetcher = CountingFetcher { calls: Rc::clone(&calls) };
+        let manager = InputManager::new(&dir).with_fetcher(Box::new(fetcher));
+
+        let path = manager.resolve(3).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input for day 3\n");
+        manager.resolve(3).unwrap();
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn missing_without_fetcher() {
+        let dir = temp_dir("no_fetcher");
+        let manager = InputManager::new(&dir);
+        assert!(matches!(manager.resolve(4), Err(FetchError::Missing(_))));
+    }
+
+    #[test]
+    fn mirror_copies_file() {
+        let mirror = temp_dir("mirror_source");
+        fs::write(mirror.join("day7.txt"), "32T3K 765\n").unwrap();
+        let fetcher = MirrorFetcher::new(&mirror);
+        assert_eq!(fetcher.fetch(7).unwrap(), "32T3K 765\n");
+        assert!(fetcher.fetch(8).is_err());
+    }
+
+    #[test]
+    fn http_fetch_sends_session() {
+        let (port, server) = stub_server("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1 2 3\n");
+        let fetcher = HttpFetcher::new("127.0.0.1", port, 2023, "abc123");
+        let body = fetcher.fetch(9).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(body, "1 2 3\n");
+        assert!(request.starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc123\r\n"));
+        assert!(request.contains(&format!("Host: 127.0.0.1:{port}\r\n")));
+    }
+
+    #[test]
+    fn http_fetch_chunked() {
+        let (port, server) = stub_server("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nab\nc\r\n3\r\nde\n\r\n0\r\n\r\n");
+        let fetcher = HttpFetcher::new("127.0.0.1", port, 2023, "abc123");
+        assert_eq!(fetcher.fetch(1).unwrap(), "ab\ncde\n");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn http_fetch_error_status() {
+        let (port, server) = stub_server("HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n");
+        let fetcher = HttpFetcher::new("127.0.0.1", port, 2023, "expired");
+        assert!(matches!(fetcher.fetch(1), Err(FetchError::Status(400))));
+        server.join().unwrap();
+    }
+}
+
+/// Somewhere a day's puzzle input can be fetched from when it isn't cached yet
+pub trait Fetcher {
+    fn fetch(&self, day: u32) -> Result<String, FetchError>;
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// Not cached, and there is no fetcher to get it
+    Missing(PathBuf),
+    Io(io::Error),
+    /// The server answered with something other than `200 OK`
+    Status(u16),
+    Malformed(String)
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing(path) => write!(f, "{} is missing and no fetcher is configured", path.display()),
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Status(status) if (300..400).contains(status) => write!(
+                f,
+                "server redirected ({status}); the HTTP fetcher doesn't speak TLS, so point it at a plain HTTP proxy"
+            ),
+            Self::Status(status) => write!(f, "server answered with status {status}"),
+            Self::Malformed(what) => write!(f, "malformed response: {what}")
+        }
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Maps days to files in a cache directory, fetching any that are missing
+pub struct InputManager {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetcher>>
+}
+
+impl InputManager {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self { dir: dir.as_ref().to_path_buf(), fetcher: None }
+    }
+
+    pub fn with_fetcher(mut self, fetcher: Box<dyn Fetcher>) -> Self {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day}.txt"))
+    }
+
+    /// Path to the day's input, fetching and caching it first if needed
+    pub fn resolve(&self, day: u32) -> Result<PathBuf, FetchError> {
+        let path = self.path(day);
+        if path.exists() {
+            return Ok(path);
+        }
+        let fetcher = self.fetcher.as_ref().ok_or_else(|| FetchError::Missing(path.clone()))?;
+        let contents = fetcher.fetch(day)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
+/// Copies inputs from another directory laid out the same way, e.g. a shared drive
+pub struct MirrorFetcher {
+    dir: PathBuf
+}
+
+impl MirrorFetcher {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self { dir: dir.as_ref().to_path_buf() }
+    }
+}
+
+impl Fetcher for MirrorFetcher {
+    fn fetch(&self, day: u32) -> Result<String, FetchError> {
+        Ok(fs::read_to_string(self.dir.join(format!("day{day}.txt")))?)
+    }
+}
+
+/// Minimal HTTP/1.1 client for `GET /<year>/day/<day>/input` with a session cookie.
+/// Plain TCP only: the real site needs HTTPS, which means going through a local proxy.
+pub struct HttpFetcher {
+    host: String,
+    port: u16,
+    year: u32,
+    session: String
+}
+
+impl HttpFetcher {
+    pub fn new(host: &str, port: u16, year: u32, session: &str) -> Self {
+        Self { host: String::from(host), port, year, session: String::from(session) }
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u32) -> Result<String, FetchError> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        // the port is part of the host header unless it is the default one
+        let host = match self.port {
+            80 => self.host.clone(),
+            port => format!("{}:{port}", self.host)
+        };
+        let request = format!(
+            "GET /{}/day/{day}/input HTTP/1.1\r\nHost: {host}\r\nCookie: session={}\r\nUser-Agent: advent_of_code_2023 input fetcher\r\nConnection: close\r\n\r\n",
+            self.year, self.session
+        );
+        stream.write_all(request.as_bytes())?;
+
+        let mut reader = BufReader::new(stream);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line)?;
+        let status: u16 = status_line
+            .split(' ')
+            .nth(1)
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| FetchError::Malformed(format!("status line {status_line:?}")))?;
+
+        let mut content_length: Option<usize> = None;
+        let mut chunked = false;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(FetchError::Malformed(String::from("headers ended early")));
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                let value = value.trim();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => content_length = value.parse().ok(),
+                    "transfer-encoding" => chunked = value.eq_ignore_ascii_case("chunked"),
+                    _ => ()
+                }
+            }
+        }
+
+        if status != 200 {
+            return Err(FetchError::Status(status));
+        }
+
+        let body = if chunked {
+            read_chunked(&mut reader)?
+        } else if let Some(len) = content_length {
+            let mut body = vec![0u8; len];
+            reader.read_exact(&mut body)?;
+            body
+        } else {
+            let mut body = Vec::new();
+            reader.read_to_end(&mut body)?;
+            body
+        };
+
+        String::from_utf8(body).map_err(|_| FetchError::Malformed(String::from("body is not UTF-8")))
+    }
+}
+
+fn read_chunked(reader: &mut impl BufRead) -> Result<Vec<u8>, FetchError> {
+    let mut body = Vec::new();
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line)?;
+        let size_text = size_line.trim().split(';').next().unwrap_or("");
+        let size = usize::from_str_radix(size_text, 16)
+            .map_err(|_| FetchError::Malformed(format!("chunk size {size_text:?}")))?;
+        if size == 0 {
+            return Ok(body);
+        }
+        let mut chunk = vec![0u8; size + 2];
+        reader.read_exact(&mut chunk)?;
+        chunk.truncate(size);
+        body.append(&mut chunk);
+    }
+}
@@ -1,85 +1,362 @@
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use config::{Config, OutputFormat, Overrides};
+use options::PuzzleOptions;
+use runner::Outcome;
 
 mod utilities;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
+mod runner;
+mod config;
+mod trace;
+mod inputs;
+mod options;
+mod automaton;
+mod rng;
+mod rational;
+mod generate;
+mod examples;
+mod history;
+mod watch;
+#[cfg(test)]
+mod property;
+#[cfg(test)]
+mod snapshot;
+#[cfg(feature = "alloc_stats")]
+mod alloc_stats;
+mod year2023;
+
+#[cfg(feature = "alloc_stats")]
+#[global_allocator]
+static ALLOCATOR: alloc_stats::CountingAllocator = alloc_stats::CountingAllocator;
 
 fn main() {
     let mut args = env::args();
     args.next();
-    let result = if let Some(problem) = args.next()  {
-        match problem.as_str() {
-            "1-1" => day1::part1(),
-            "1-2" => day1::part2(),
-            "2-1" => day2::part1(),
-            "2-2" => day2::part2(),
-            "3-1" => day3::part1("./input/day3.txt"),
-            "3-2" => day3::part2("./input/day3.txt"),
-            "4-1" => day4::part1("./input/day4.txt"),
-            "4-2" => day4::part2("./input/day4.txt"),
-            "5-1" => day5::part1("./input/day5.txt"),
-            "5-2" => day5::part2("./input/day5.txt"),
-            "6-1" => day6::part1("./input/day6.txt"),
-            "6-2" => day6::part2("./input/day6.txt"),
-            "7-1" => day7::part1("./input/day7.txt"),
-            "7-2" => day7::part2("./input/day7.txt"),
-            "8-1" => day8::part1("./input/day8.txt"),
-            "8-2" => day8::part2("./input/day8.txt"),
-            "9-1" => day9::part1("./input/day9.txt"),
-            "9-2" => day9::part2("./input/day9.txt"),
-            "10-1" => day10::part1("./input/day10.txt"),
-            "10-2" => day10::part2("./input/day10.txt"),
-            "11-1" => day11::part1("./input/day11.txt"),
-            "11-2" => day11::part2("./input/day11.txt"),
-            "12-1" => day12::part1("./input/day12.txt"),
-            "12-2" => day12::part2("./input/day12.txt"),
-            "13-1" => day13::part1("./input/day13.txt"),
-            "13-2" => day13::part2("./input/day13.txt"),
-            "14-1" => day14::part1("./input/day14.txt"),
-            "14-2" => day14::part2("./input/day14.txt"),
-            "15-1" => day15::part1("./input/day15.txt"),
-            "15-2" => day15::part2("./input/day15.txt"),
-            "16-1" => day16::part1("./input/day16.txt"),
-            "16-2" => day16::part2("./input/day16.txt"),
-            "17-1" => day17::part1("./input/day17.txt"),
-            "17-2" => day17::part2("./input/day17.txt"),
-            "18-1" => day18::part1("./input/day18.txt"),
-            "18-2" => day18::part2("./input/day18.txt"),
-            "19-1" => day19::part1("./input/day19.txt"),
-            "19-2" => day19::part2("./input/day19.txt"),
-            "20-1" => day20::part1("./input/day20.txt"),
-            "20-2" => day20::part2("./input/day20.txt"),
-            "21-1" => day21::part1("./input/day21.txt"),
-            // "21-2" => day21::part2("./input/day21.txt"),
-            // "16-1" => day16::part1("./input/day16.txt"),
-            // "16-2" => day16::part2("./input/day16.txt"),
-            // "13-1" => day13::part1("./input/day13.txt"),
-            // "13-2" => day13::part2("./input/day13.txt"),
-
-            _ => String::from("Problem not implemented")
+    let problems: Vec<String> = args.collect();
+    match problems.first().map(| s | s.as_str()) {
+        None => println!("No argument"),
+        Some("generate") => generate_command(&problems[1..]),
+        Some("examples") => examples::examples_command(&problems[1..]),
+        Some("run") => run_command(&problems[1..]),
+        Some("watch") => watch::watch_command(&problems[1..]),
+        Some("history") => history_command(&problems[1..]),
+        Some("export") => export_command(&problems[1..]),
+        Some(_) => run_problems(problems)
+    }
+}
+
+/// `run <year> <day> <part> [options]`
+fn run_command(args: &[String]) {
+    if let [year, day, part, options @ ..] = args {
+        let mut problems = vec![format!("{year}-{day}-{part}")];
+        problems.extend(options.iter().cloned());
+        run_problems(problems);
+    } else {
+        println!("Usage: run <year> <day> <part> [options]");
+    }
+}
+
+/// What happened to one problem, ready to print
+struct Report {
+    problem: String,
+    output: OutputFormat,
+    /// The input file, once it was found
+    input: Option<PathBuf>,
+    /// The puzzle options given, which history keeps runs apart by
+    puzzle: PuzzleOptions,
+    result: Result<runner::RunRecord, String>
+}
+
+/// Runs problems given as `day-part` (for the default year) or `year-day-part`.
+/// Options such as `--timeout-ms 500` override the config file, which `--config <path>` can point elsewhere.
+fn run_problems(args: Vec<String>) {
+    let (problems, cli, config_path) = match split_options(&args) {
+        Ok(split) => split,
+        Err(message) => {
+            println!("{message}");
+            return;
+        }
+    };
+    let config = match Config::load(&config_path) {
+        Ok(config) => config,
+        Err(err) => {
+            println!("Bad config file {}: {err}", config_path.display());
+            return;
+        }
+    };
+
+    // workers pull problems off a shared counter; reports are printed in the order given.
+    // Allocation counts are process wide, so counting them means solving one problem at a time.
+    let workers = if cfg!(feature = "alloc_stats") { 1 } else { config.global_settings(&cli).workers.min(problems.len()) };
+    let next = AtomicUsize::new(0);
+    let reports: Mutex<Vec<Option<Report>>> = Mutex::new((0..problems.len()).map(| _ | None).collect());
+    thread::scope(
+        | scope | {
+            for _ in 0..workers {
+                scope.spawn(
+                    || {
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(problem) = problems.get(index) else { break };
+                            let report = solve_problem(problem, &config, &cli);
+                            reports.lock().unwrap()[index] = Some(report);
+                        }
+                    }
+                );
+            }
+        }
+    );
+
+    let reports: Vec<Report> = reports.into_inner().unwrap().into_iter().flatten().collect();
+    record_history(&config.global_settings(&cli).history, &reports);
+
+    let mut records = Vec::new();
+    for report in reports {
+        match (report.output, report.result) {
+            (OutputFormat::Text, Ok(record)) => {
+                println!("{}", record.answer);
+                records.push(record);
+            },
+            (OutputFormat::Text, Err(message)) => println!("{message}"),
+            (OutputFormat::Json, Ok(record)) => println!(
+                "{{\"problem\": {}, \"answer\": {}, \"ms\": {:.3}}}",
+                json_string(&report.problem),
+                json_string(&record.answer),
+                record.duration.as_secs_f64() * 1000.0
+            ),
+            (OutputFormat::Json, Err(message)) => println!(
+                "{{\"problem\": {}, \"error\": {}}}",
+                json_string(&report.problem),
+                json_string(&message)
+            )
+        }
+    }
+
+    if !records.is_empty() {
+        println!();
+        print!("{}", runner::timing_table(&records));
+    }
+}
+
+/// Appends every solved problem to the history file, warning about answers that differ from the accepted one
+fn record_history(path: &Path, reports: &[Report]) {
+    let previous = history::read(path);
+    let time = history::now();
+    let commit = history::current_commit();
+    let mut entries = Vec::new();
+    for report in reports {
+        let (Some(input), Ok(record)) = (&report.input, &report.result) else { continue };
+        let input = input.to_string_lossy().into_owned();
+        let options = history::options_key(&report.puzzle);
+        let same_problem: Vec<history::Entry> = previous
+            .iter()
+            .filter(| entry | (entry.year, entry.day, entry.part) == (record.year, record.day, record.part))
+            .cloned()
+            .collect();
+        if let Some(accepted) = history::accepted_answer(&same_problem, &input, &options) {
+            if accepted != record.answer {
+                eprintln!("warning: {} answer differs from the accepted {accepted:?}", report.problem);
+            }
+        }
+        entries.push(history::Entry {
+            kind: history::Kind::Run,
+            time,
+            commit: commit.clone(),
+            year: record.year,
+            day: record.day,
+            part: record.part,
+            input,
+            options,
+            duration: Some(record.duration),
+            answer: record.answer.clone()
+        });
+    }
+    if !entries.is_empty() {
+        if let Err(err) = history::append(path, &entries) {
+            eprintln!("Could not write history to {}: {err}", path.display());
+        }
+    }
+}
+
+/// `history <day>-<part> [--accept] [--config <path>]` shows past runs of a problem.
+/// `--accept` makes the latest run's answer the one later runs on that input are checked against.
+fn history_command(args: &[String]) {
+    let Some((year, day, part)) = args.first().and_then(| problem | runner::parse_problem(problem)) else {
+        println!("Usage: history <day>-<part> [--accept] [options]");
+        return;
+    };
+    let accept = args.iter().any(| arg | arg == "--accept");
+    let options: Vec<String> = args[1..].iter().filter(| arg | *arg != "--accept").cloned().collect();
+    let path = match split_options(&options).map(| (_, cli, config_path) | (cli, Config::load(&config_path))) {
+        Ok((cli, Ok(config))) => config.global_settings(&cli).history,
+        Ok((_, Err(err))) => {
+            println!("Bad config file: {err}");
+            return;
+        },
+        Err(message) => {
+            println!("{message}");
+            return;
+        }
+    };
+
+    let mut entries: Vec<history::Entry> = history::read(&path)
+        .into_iter()
+        .filter(| entry | (entry.year, entry.day, entry.part) == (year, day, part))
+        .collect();
+    if accept {
+        let Some(latest) = entries.iter().rev().find(| entry | entry.kind == history::Kind::Run) else {
+            println!("No runs of {year}-{day}-{part} to accept");
+            return;
+        };
+        let accepted = history::Entry { kind: history::Kind::Accept, time: history::now(), duration: None, ..latest.clone() };
+        if let Err(err) = history::append(&path, std::slice::from_ref(&accepted)) {
+            println!("Could not write history to {}: {err}", path.display());
+            return;
         }
+        entries.push(accepted);
+    }
+    if entries.is_empty() {
+        println!("No history for {year}-{day}-{part} in {}", path.display());
+        return;
+    }
+
+    // one block per input and options, each in the order the runs happened
+    entries.sort_by(| a, b | (&a.input, &a.options).cmp(&(&b.input, &b.options)));
+    print!("{}", history::history_table(&entries));
+}
+
+fn solve_problem(problem: &str, config: &Config, cli: &Overrides) -> Report {
+    let Some((year, day, part)) = runner::parse_problem(problem)
+        .filter(| (year, day, part) | runner::solver(*year, *day, *part).is_some()) else {
+        return Report {
+            problem: String::from(problem),
+            output: config.global_settings(cli).output,
+            input: None,
+            puzzle: PuzzleOptions::new(),
+            result: Err(String::from("Problem not implemented"))
+        };
+    };
+    let settings = config.settings(year, day, cli);
+    let problem = format!("{year}-{day}-{part}");
+
+    let path = match &settings.input {
+        Some(path) => path.clone(),
+        None => match input_manager(&settings.input_dir, year).and_then(| manager | manager.resolve(day).map_err(| err | err.to_string())) {
+            Ok(path) => path,
+            Err(err) => return Report {
+                problem,
+                output: settings.output,
+                input: None,
+                puzzle: settings.puzzle.clone(),
+                result: Err(format!("Could not get input for {year} day {day}: {err}"))
+            }
+        }
+    };
+    let report = | result | Report {
+        problem: problem.clone(),
+        output: settings.output,
+        input: Some(path.clone()),
+        puzzle: settings.puzzle.clone(),
+        result
+    };
+    let outcome = runner::run_limited(year, day, part, path.to_string_lossy().into_owned(), settings.timeout, settings.trace, settings.puzzle.clone())
+        .expect("solver was checked above");
+    match outcome {
+        Outcome::Solved(record) => report(Ok(record)),
+        Outcome::TimedOut(limit) => report(Err(format!("{problem} timed out after {} ms", limit.as_millis()))),
+        Outcome::Panicked => report(Err(format!("{problem} panicked")))
+    }
+}
+
+/// Separates problems from `--<setting> <value>` options, returning the config file path too
+fn split_options(args: &[String]) -> Result<(Vec<String>, Overrides, PathBuf), String> {
+    let mut problems = Vec::new();
+    let mut cli = Overrides::default();
+    let mut config_path = PathBuf::from(Config::DEFAULT_PATH);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(option) = arg.strip_prefix("--") else {
+            problems.push(arg.clone());
+            continue;
+        };
+        let value = args.next().ok_or_else(|| format!("Missing value for {arg}"))?;
+        if option == "config" {
+            config_path = PathBuf::from(value);
+        } else {
+            cli.set(&option.replace('-', "_"), value)?;
+        }
+    }
+    Ok((problems, cli, config_path))
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            ch if ch.is_control() => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch)
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Inputs are cached in `<input_dir>/<year>`. Missing ones are copied from `AOC_INPUT_MIRROR/<year>` if set,
+/// otherwise downloaded with the `AOC_SESSION` cookie from `AOC_HOST` (`host` or `host:port`).
+fn input_manager(input_dir: &Path, year: u32) -> Result<inputs::InputManager, String> {
+    let manager = inputs::InputManager::new(input_dir.join(year.to_string()));
+    if let Ok(mirror) = env::var("AOC_INPUT_MIRROR") {
+        let mirror = Path::new(&mirror).join(year.to_string());
+        return Ok(manager.with_fetcher(Box::new(inputs::MirrorFetcher::new(mirror))));
+    }
+    if let Ok(session) = env::var("AOC_SESSION") {
+        let host = env::var("AOC_HOST").unwrap_or_else(| _ | String::from("adventofcode.com"));
+        let (host, port) = match host.split_once(':') {
+            Some((host, port)) => match port.parse() {
+                Ok(port) => (String::from(host), port),
+                Err(_) => return Err(format!("Bad port {port:?} in AOC_HOST, expected a number"))
+            },
+            None => (host, 80)
+        };
+        return Ok(manager.with_fetcher(Box::new(inputs::HttpFetcher::new(&host, port, year, &session))));
+    }
+    Ok(manager)
+}
+
+/// `generate <day> [--seed N] [--size S]` prints a random input for that day of 2023
+fn generate_command(args: &[String]) {
+    let day: Option<u32> = args.first().and_then(| s | s.parse().ok());
+    let seed: u64 = flag_value(args, "--seed").unwrap_or(0);
+    let size: usize = flag_value(args, "--size").unwrap_or(10);
+    if let Some(input) = day.and_then(| day | generate::generate(day, seed, size)) {
+        print!("{input}");
     } else {
-        String::from("No argument")
+        println!("Usage: generate <day> [--seed N] [--size S] (days 1 to 21)");
+    }
+}
+
+/// `export <day> <input> <graph.dot|graph.json>` writes a day's puzzle structure as a graph; only day 3 has one
+fn export_command(args: &[String]) {
+    let [day, input, output] = args else {
+        println!("Usage: export 3 <input> <graph.dot|graph.json>");
+        return;
     };
+    if day != "3" {
+        println!("Only day 3 can be exported");
+        return;
+    }
+    println!("{}", year2023::day3::export(input, Path::new(output)).unwrap_or_else(| message | message));
+}
 
-    println!("{result}");
+/// Parses the value following `flag`, if it is present
+fn flag_value<T: std::str::FromStr>(args: &[String], flag: &str) -> Option<T> {
+    let position = args.iter().position(| arg | arg == flag)?;
+    args.get(position + 1)?.parse().ok()
 }
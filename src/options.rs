@@ -0,0 +1,41 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn options_are_per_thread() {
+        set(BTreeMap::from([(String::from("threads"), String::from("4"))]));
+        assert_eq!(get_or("threads", 1), 4);
+        assert_eq!(get_or("vocabulary", String::from("english")), "english");
+        std::thread::spawn(|| assert_eq!(get("threads"), None)).join().unwrap();
+    }
+}
+
+/// Named settings for the puzzle being solved, given as `--puzzle-<name> <value>` or `puzzle_<name> = value`
+pub type PuzzleOptions = BTreeMap<String, String>;
+
+thread_local! {
+    // set by the runner on the thread that runs the solver, like the trace level
+    static OPTIONS: RefCell<PuzzleOptions> = const { RefCell::new(PuzzleOptions::new()) };
+}
+
+pub fn set(options: PuzzleOptions) {
+    OPTIONS.with(| current | *current.borrow_mut() = options);
+}
+
+pub fn get(name: &str) -> Option<String> {
+    OPTIONS.with(| current | current.borrow().get(name).cloned())
+}
+
+/// The option parsed as `T`, or `default` if it wasn't given. Panics on a value that doesn't parse.
+pub fn get_or<T: FromStr>(name: &str, default: T) -> T where T::Err: Display {
+    match get(name) {
+        Some(value) => value.parse().unwrap_or_else(| err | panic!("Bad value {value:?} for puzzle option {name}: {err}")),
+        None => default
+    }
+}
@@ -0,0 +1,173 @@
+use crate::rng::Rng;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    /// A list of numbers, shrunk by dropping entries or lowering them
+    #[derive(Clone, Debug)]
+    struct Numbers(Vec<u32>);
+
+    impl Arbitrary for Numbers {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            Self((0..rng.range(0..size + 1)).map(|_| rng.below(100) as u32).collect())
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            for i in 0..self.0.len() {
+                let mut fewer = self.0.clone();
+                fewer.remove(i);
+                smaller.push(Self(fewer));
+                if self.0[i] > 0 {
+                    let mut lower = self.0.clone();
+                    lower[i] /= 2;
+                    smaller.push(Self(lower));
+                }
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn passing_property() {
+        let result = check(1, 50, 10, |nums: &Numbers| {
+            let total: u32 = nums.0.iter().sum();
+            agree(&total, &nums.0.iter().rev().sum())
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn shrinks_to_smallest_failure() {
+        let result = check(1, 50, 10, |nums: &Numbers| {
+            let total: u32 = nums.0.iter().sum();
+            agree(&total.min(20), &total)
+        });
+        let failure = result.unwrap_err();
+        // still fails, but every smaller candidate passes
+        assert!(failure.shrunk.0.iter().sum::<u32>() > 20);
+        for candidate in failure.shrunk.shrink() {
+            assert!(candidate.0.iter().sum::<u32>() <= 20);
+        }
+    }
+
+    #[test]
+    fn panics_count_as_failures() {
+        let result = check(3, 20, 5, |nums: &Numbers| {
+            if nums.0.len() > 2 {
+                panic!("too long");
+            }
+            Ok(())
+        });
+        let failure = result.unwrap_err();
+        assert_eq!(failure.shrunk.0, vec![0, 0, 0]);
+        assert!(failure.message.contains("too long"));
+    }
+}
+
+/// Values that can be randomly generated and then made smaller when a property fails
+pub trait Arbitrary: Clone + Debug {
+    /// A random value; `size` bounds how large it can be
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self;
+
+    /// Candidate values that are strictly smaller in some sense. Empty when fully shrunk.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+/// The first failing case found, along with the smallest variant of it that still fails
+#[derive(Debug)]
+pub struct Counterexample<T> {
+    pub seed: u64,
+    pub case: usize,
+    pub original: T,
+    pub shrunk: T,
+    pub shrink_steps: usize,
+    pub message: String
+}
+
+impl<T: Debug> Counterexample<T> {
+    pub fn report(&self) -> String {
+        format!(
+            "Property failed on case {} with seed {}\nOriginal case: {:?}\n{}\nShrunk in {} steps to:\n{:#?}",
+            self.case, self.seed, self.original, self.message, self.shrink_steps, self.shrunk
+        )
+    }
+}
+
+/// Checks `property` against `cases` random values, growing the size limit up to `max_size`.
+/// A panic inside the property counts as a failure.
+pub fn check<T: Arbitrary>(
+    seed: u64,
+    cases: usize,
+    max_size: usize,
+    property: impl Fn(&T) -> Result<(), String>
+) -> Result<(), Counterexample<T>> {
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let size = 1 + case * max_size / cases.max(1);
+        let value = T::arbitrary(&mut rng, size);
+        if let Err(message) = run_property(&property, &value) {
+            let (shrunk, shrink_steps, message) = shrink_failure(&property, value.clone(), message);
+            return Err(Counterexample { seed, case, original: value, shrunk, shrink_steps, message });
+        }
+    }
+    Ok(())
+}
+
+/// Panics with the counterexample report if the property fails, for use inside `#[test]`s
+pub fn assert_property<T: Arbitrary>(
+    seed: u64,
+    cases: usize,
+    max_size: usize,
+    property: impl Fn(&T) -> Result<(), String>
+) {
+    if let Err(failure) = check(seed, cases, max_size, property) {
+        panic!("{}", failure.report());
+    }
+}
+
+/// `Ok` when the two results match, otherwise an error describing both
+pub fn agree<R: PartialEq + Debug>(naive: &R, optimized: &R) -> Result<(), String> {
+    if naive == optimized {
+        Ok(())
+    } else {
+        Err(format!("naive gave {naive:?}, optimized gave {optimized:?}"))
+    }
+}
+
+fn run_property<T>(property: &impl Fn(&T) -> Result<(), String>, value: &T) -> Result<(), String> {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| property(value)));
+
+    match outcome {
+        Ok(result) => result,
+        Err(payload) => {
+            let text = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown panic"));
+            Err(format!("panicked: {text}"))
+        }
+    }
+}
+
+/// Greedily takes the first smaller candidate that still fails until none do
+fn shrink_failure<T: Arbitrary>(
+    property: &impl Fn(&T) -> Result<(), String>,
+    mut value: T,
+    mut message: String
+) -> (T, usize, String) {
+    let mut steps = 0;
+    'outer: loop {
+        for candidate in value.shrink() {
+            if let Err(new_message) = run_property(property, &candidate) {
+                value = candidate;
+                message = new_message;
+                steps += 1;
+                continue 'outer;
+            }
+        }
+        return (value, steps, message);
+    }
+}
@@ -0,0 +1,335 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn big_arithmetic() {
+        let a = BigInt::from(i128::MAX);
+        let b = BigInt::from(-12345678901234567890i128);
+        let product = &a * &b;
+        let (quotient, remainder) = product.div_rem(&b);
+        assert_eq!(quotient, a);
+        assert!(remainder.is_zero());
+        assert_eq!((&product + &a).div_rem(&a).0, &b + &BigInt::from(1));
+        assert_eq!((&a - &a), BigInt::from(0));
+        assert_eq!(BigInt::from(-7).div_rem(&BigInt::from(2)), (BigInt::from(-3), BigInt::from(-1)));
+    }
+
+    #[test]
+    fn rational_arithmetic() {
+        let third = Rational::new(1, 3);
+        let half = Rational::new(-2, -4);
+        assert_eq!(&third + &half, Rational::new(5, 6));
+        assert_eq!(&third - &half, Rational::new(-1, 6));
+        assert_eq!(&(&third / &half) * &Rational::from(3), Rational::from(2));
+        assert_eq!(Rational::new(6, 3).to_integer(), Some(2));
+        assert_eq!(third.to_integer(), None);
+    }
+}
+
+/// Signed integer of any size, for exact arithmetic that would overflow `i128`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    /// Base 2^32 digits, least significant first, with no trailing zeros. Zero is empty.
+    digits: Vec<u32>
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut digits: Vec<u32>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        let negative = negative && !digits.is_empty();
+        Self { negative, digits }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        Self { negative: false, digits: self.digits.clone() }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.digits.len() > 4 {
+            return None;
+        }
+        let magnitude = self.digits.iter().rev().fold(0u128, | acc, digit | (acc << 32) | *digit as u128);
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    /// Quotient rounded toward zero, and the remainder with the sign of `self`
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        if other.is_zero() {
+            panic!("Division by zero");
+        }
+        let (quotient, remainder) = div_rem_digits(&self.digits, &other.digits);
+        (
+            Self::from_parts(self.negative != other.negative, quotient),
+            Self::from_parts(self.negative, remainder)
+        )
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = Vec::new();
+        while magnitude > 0 {
+            digits.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        Self::from_parts(value < 0, digits)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(value) = self.to_i128() {
+            return write!(f, "{value}");
+        }
+        let billion = Self::from(1_000_000_000);
+        let mut chunks = Vec::new();
+        let mut rest = self.abs();
+        while !rest.is_zero() {
+            let (quotient, remainder) = rest.div_rem(&billion);
+            chunks.push(remainder.to_i128().unwrap());
+            rest = quotient;
+        }
+        let mut text = String::from(if self.negative { "-" } else { "" });
+        text.push_str(&chunks.pop().unwrap().to_string());
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{chunk:09}"));
+        }
+        write!(f, "{text}")
+    }
+}
+
+fn compare_digits(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let total = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+/// `a - b` for `a >= b`
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, digit) in a.iter().enumerate() {
+        let mut total = *digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    difference
+}
+
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let total = product[i + j] as u64 + *x as u64 * *y as u64 + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+/// Binary long division; the numbers here are only a few hundred bits long
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for bit in (0..a.len() * 32).rev() {
+        // remainder = remainder * 2 + next bit of a
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for digit in remainder.iter_mut() {
+            let shifted_out = *digit >> 31;
+            *digit = (*digit << 1) | carry;
+            carry = shifted_out;
+        }
+        if carry == 1 {
+            remainder.push(1);
+        }
+        if compare_digits(&remainder, b) != Ordering::Less {
+            remainder = sub_digits(&remainder, b);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_digits(&self.digits, &other.digits));
+        }
+        match compare_digits(&self.digits, &other.digits) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_digits(&other.digits, &self.digits)),
+            _ => BigInt::from_parts(self.negative, sub_digits(&self.digits, &other.digits))
+        }
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits.clone())
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, mul_digits(&self.digits, &other.digits))
+    }
+}
+
+/// Exact fraction, always in lowest terms with a positive denominator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt
+}
+
+impl Rational {
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        Self::reduce(BigInt::from(numerator), BigInt::from(denominator))
+    }
+
+    fn reduce(numerator: BigInt, denominator: BigInt) -> Self {
+        if denominator.is_zero() {
+            panic!("Zero denominator");
+        }
+        let divisor = numerator.gcd(&denominator);
+        let divisor = if denominator.is_negative() { -&divisor } else { divisor };
+        if numerator.is_zero() {
+            return Self { numerator, denominator: BigInt::from(1) };
+        }
+        Self {
+            numerator: numerator.div_rem(&divisor).0,
+            denominator: denominator.div_rem(&divisor).0
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    /// The value as an `i128`, if it is a whole number that fits
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.denominator == BigInt::from(1) {
+            self.numerator.to_i128()
+        } else {
+            None
+        }
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self { numerator: BigInt::from(value), denominator: BigInt::from(1) }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == BigInt::from(1) {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+
+    fn add(self, other: &Rational) -> Rational {
+        Rational::reduce(
+            &(&self.numerator * &other.denominator) + &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator
+        )
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+
+    fn sub(self, other: &Rational) -> Rational {
+        Rational::reduce(
+            &(&self.numerator * &other.denominator) - &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator
+        )
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+
+    fn mul(self, other: &Rational) -> Rational {
+        Rational::reduce(&self.numerator * &other.numerator, &self.denominator * &other.denominator)
+    }
+}
+
+impl Div for &Rational {
+    type Output = Rational;
+
+    fn div(self, other: &Rational) -> Rational {
+        Rational::reduce(&self.numerator * &other.denominator, &self.denominator * &other.numerator)
+    }
+}
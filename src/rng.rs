@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+        let first_vals: Vec<u64> = (0..10).map(|_| first.next_u64()).collect();
+        let second_vals: Vec<u64> = (0..10).map(|_| second.next_u64()).collect();
+        assert_eq!(first_vals, second_vals);
+    }
+
+    #[test]
+    fn range_stays_in_bounds() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let val = rng.range(3..9);
+            assert!((3..9).contains(&val));
+        }
+    }
+}
+
+/// Small deterministic PRNG (SplitMix64), so that a seed always reproduces the same input
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`. Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            panic!("Empty range for random value");
+        }
+        self.next_u64() % n
+    }
+
+    /// Uniform value in the given (non-empty) range
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        range.start + self.below((range.end - range.start) as u64) as usize
+    }
+
+    /// `true` with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range(0..i+1);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "alloc_stats")]
+use crate::alloc_stats::{self, AllocStats};
+use crate::options::{self, PuzzleOptions};
+use crate::trace::{self, TraceLevel};
+use crate::year2023;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn problems_with_and_without_year() {
+        assert_eq!(parse_problem("17-2"), Some((DEFAULT_YEAR, 17, 2)));
+        assert_eq!(parse_problem("2022-5-1"), Some((2022, 5, 1)));
+        assert_eq!(parse_problem("5"), None);
+        assert!(solver(2023, 5, 2).is_some());
+        assert!(solver(2022, 5, 2).is_none());
+    }
+}
+
+/// Every solver takes the path to its input file and returns the answer text
+pub type Solver = fn(&str) -> String;
+
+/// Year assumed when a problem is given as just day and part
+pub const DEFAULT_YEAR: u32 = 2023;
+
+/// Splits a problem argument such as `"17-2"` or `"2022-17-2"` into year, day and part
+pub fn parse_problem(problem: &str) -> Option<(u32, u32, u32)> {
+    let numbers: Vec<u32> = problem.split('-').map(| s | s.parse().ok()).collect::<Option<_>>()?;
+    match numbers[..] {
+        [day, part] => Some((DEFAULT_YEAR, day, part)),
+        [year, day, part] => Some((year, day, part)),
+        _ => None
+    }
+}
+
+/// Each year's solutions live in their own `yearYYYY` module with its own table
+pub fn solver(year: u32, day: u32, part: u32) -> Option<Solver> {
+    match year {
+        2023 => year2023::solver(day, part),
+        _ => None
+    }
+}
+
+/// The answer to one problem along with what it cost to compute
+pub struct RunRecord {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub duration: Duration,
+    #[cfg(feature = "alloc_stats")]
+    pub allocations: AllocStats
+}
+
+/// Runs a single problem on the input at `path`, timing it (and counting its allocations with `alloc_stats`).
+/// Returns `None` if there is no solver for that day and part.
+pub fn run(year: u32, day: u32, part: u32, path: &str) -> Option<RunRecord> {
+    let solver = solver(year, day, part)?;
+
+    let timed = || {
+        let start = Instant::now();
+        let answer = solver(path);
+        (answer, start.elapsed())
+    };
+    #[cfg(not(feature = "alloc_stats"))]
+    let (answer, duration) = timed();
+    #[cfg(feature = "alloc_stats")]
+    let ((answer, duration), allocations) = alloc_stats::measure(timed);
+
+    Some(RunRecord {
+        year,
+        day,
+        part,
+        answer,
+        duration,
+        #[cfg(feature = "alloc_stats")]
+        allocations
+    })
+}
+
+/// How a problem run on its own thread ended
+pub enum Outcome {
+    Solved(RunRecord),
+    TimedOut(Duration),
+    /// The solver panicked; the panic message has already gone to stderr
+    Panicked
+}
+
+/// Like `run`, but on a separate thread that is abandoned if it takes longer than `timeout`.
+/// `trace` sets the trace level for that thread and `puzzle` the options the solver can read.
+pub fn run_limited(
+    year: u32,
+    day: u32,
+    part: u32,
+    path: String,
+    timeout: Option<Duration>,
+    trace: TraceLevel,
+    puzzle: PuzzleOptions
+) -> Option<Outcome> {
+    solver(year, day, part)?;
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        // some solvers recurse deeply, so give them far more stack than the spawn default
+        .stack_size(64 * 1024 * 1024)
+        .spawn(
+            move || {
+                trace::set_level(trace);
+                options::set(puzzle);
+                trace::info(|| format!("{year}-{day}-{part}: reading {path}"));
+                let record = run(year, day, part, &path).expect("solver was checked above");
+                trace::info(|| format!("{year}-{day}-{part}: finished in {:?}", record.duration));
+                trace::debug(|| format!("{year}-{day}-{part}: answered {:?}", record.answer));
+                _ = sender.send(record);
+            }
+        )
+        .expect("Could not start solver thread");
+
+    let outcome = match timeout {
+        Some(limit) => match receiver.recv_timeout(limit) {
+            Ok(record) => Outcome::Solved(record),
+            Err(RecvTimeoutError::Timeout) => Outcome::TimedOut(limit),
+            Err(RecvTimeoutError::Disconnected) => Outcome::Panicked
+        },
+        None => match receiver.recv() {
+            Ok(record) => Outcome::Solved(record),
+            Err(_) => Outcome::Panicked
+        }
+    };
+    Some(outcome)
+}
+
+pub fn timing_table(records: &[RunRecord]) -> String {
+    let mut out = String::new();
+    #[cfg(not(feature = "alloc_stats"))]
+    out.push_str(&format!("{:<12}{:>12}\n", "problem", "time (ms)"));
+    #[cfg(feature = "alloc_stats")]
+    out.push_str(&format!(
+        "{:<12}{:>12}{:>16}{:>16}{:>14}\n",
+        "problem", "time (ms)", "peak bytes", "total bytes", "allocations"
+    ));
+
+    #[cfg(feature = "alloc_stats")]
+    let mut overlapped = false;
+    for record in records {
+        let problem = format!("{}-{}-{}", record.year, record.day, record.part);
+        let millis = record.duration.as_secs_f64() * 1000.0;
+        #[cfg(not(feature = "alloc_stats"))]
+        out.push_str(&format!("{problem:<12}{millis:>12.3}\n"));
+        #[cfg(feature = "alloc_stats")]
+        if record.allocations.overlapped {
+            overlapped = true;
+            out.push_str(&format!("{problem:<12}{millis:>12.3}{:>16}{:>16}{:>14}\n", "-", "-", "-"));
+        } else {
+            out.push_str(&format!(
+                "{:<12}{:>12.3}{:>16}{:>16}{:>14}\n",
+                problem,
+                millis,
+                record.allocations.peak_bytes,
+                record.allocations.total_bytes,
+                record.allocations.allocations
+            ));
+        }
+    }
+    #[cfg(feature = "alloc_stats")]
+    if overlapped {
+        out.push_str("- another solver was allocating at the same time, such as one still running after it timed out\n");
+    }
+
+    out
+}
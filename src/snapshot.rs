@@ -0,0 +1,62 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn first_difference_is_reported() {
+        let message = difference("a\nb\nc\n", "a\nx\nc\n").unwrap();
+        assert!(message.starts_with("line 2 differs"));
+        assert!(difference("same\n", "same\n").is_none());
+        assert!(difference("short\n", "short\nlonger\n").unwrap().starts_with("line 2 differs"));
+    }
+}
+
+/// Set to re-record every snapshot a test run touches instead of comparing against it
+const UPDATE_FLAG: &str = "UPDATE_SNAPSHOTS";
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(format!("{name}.snap"))
+}
+
+/// Compares `actual` with `snapshots/<name>.snap`, panicking at the first differing line.
+/// Run the tests with `UPDATE_SNAPSHOTS=1` to record new or changed snapshots.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if env::var_os(UPDATE_FLAG).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).expect("Could not create snapshot directory");
+        fs::write(&path, actual).expect("Could not write snapshot");
+        return;
+    }
+
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!("No snapshot at {}; run with {UPDATE_FLAG}=1 to record it. Got:\n{actual}", path.display());
+    };
+    if expected != actual {
+        let message = difference(&expected, actual).unwrap_or_else(|| String::from("trailing newline differs"));
+        panic!(
+            "Snapshot {name} changed: {message}\n--- expected\n{expected}--- actual\n{actual}\
+            Run with {UPDATE_FLAG}=1 to accept the new output."
+        );
+    }
+}
+
+fn difference(expected: &str, actual: &str) -> Option<String> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line_number in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+            (e, a) if e == a => continue,
+            (e, a) => return Some(format!(
+                "line {line_number} differs\n  expected: {}\n  actual:   {}",
+                e.unwrap_or("<end>"),
+                a.unwrap_or("<end>")
+            ))
+        }
+    }
+    unreachable!()
+}
@@ -0,0 +1,64 @@
+use std::cell::Cell;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn levels_filter_messages() {
+        set_level(TraceLevel::Info);
+        assert!(enabled(TraceLevel::Info));
+        assert!(!enabled(TraceLevel::Debug));
+        set_level(TraceLevel::Off);
+        assert!(!enabled(TraceLevel::Info));
+        assert_eq!("debug".parse(), Ok(TraceLevel::Debug));
+    }
+}
+
+/// How much the runner and solvers print to stderr while working
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TraceLevel {
+    Off,
+    Info,
+    Debug
+}
+
+impl FromStr for TraceLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            _ => Err(format!("unknown trace level {s:?}, expected off, info or debug"))
+        }
+    }
+}
+
+thread_local! {
+    // per thread, so problems running side by side can trace at different levels
+    static LEVEL: Cell<TraceLevel> = const { Cell::new(TraceLevel::Off) };
+}
+
+pub fn set_level(level: TraceLevel) {
+    LEVEL.with(| current | current.set(level));
+}
+
+pub fn enabled(level: TraceLevel) -> bool {
+    LEVEL.with(| current | current.get() >= level)
+}
+
+/// Prints the message to stderr if tracing is at `Info` or above. The message is only built when needed.
+pub fn info(message: impl FnOnce() -> String) {
+    if enabled(TraceLevel::Info) {
+        eprintln!("[info] {}", message());
+    }
+}
+
+pub fn debug(message: impl FnOnce() -> String) {
+    if enabled(TraceLevel::Debug) {
+        eprintln!("[debug] {}", message());
+    }
+}
@@ -0,0 +1,245 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn notices_edits_and_new_files() {
+        let dir = env::temp_dir().join("aoc2023_watch");
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("day7.txt");
+        fs::write(&input, "1").unwrap();
+        let files = WatchedFiles { fixed: vec![input.clone()], test_dir: dir.clone(), test_prefix: String::from("day7_test") };
+
+        let before = files.snapshot();
+        assert!(changed_files(&before, &files.snapshot()).is_empty());
+
+        fs::write(dir.join("day7_test1.txt"), "example").unwrap();
+        let after = files.snapshot();
+        assert_eq!(changed_files(&before, &after), vec![dir.join("day7_test1.txt")]);
+
+        fs::remove_file(&input).unwrap();
+        assert_eq!(changed_files(&after, &files.snapshot()), vec![input]);
+    }
+
+    #[test]
+    fn reads_runner_json() {
+        let line = "{\"problem\": \"2023-7-1\", \"answer\": \"Total \\\"winnings\\\" 6440\", \"ms\": 1.250}";
+        let run = RunResult::from_json(line).unwrap();
+        assert_eq!(run.answer, "Total \"winnings\" 6440");
+        assert_eq!(run.millis, 1.25);
+        assert!(RunResult::from_json("{\"problem\": \"2023-7-1\", \"error\": \"panicked\"}").is_none());
+
+        let answer = "tab\there, \\ \"quoted\"\nbell\u{7}";
+        let line = format!("{{\"problem\": \"2023-7-1\", \"answer\": {}, \"ms\": 2.000}}", crate::json_string(answer));
+        assert_eq!(RunResult::from_json(&line).unwrap().answer, answer);
+    }
+
+    #[test]
+    fn describes_differences() {
+        let old = RunResult { answer: String::from("42"), millis: 10.0 };
+        let same = RunResult { answer: String::from("42"), millis: 5.0 };
+        let new = RunResult { answer: String::from("43"), millis: 20.0 };
+        assert_eq!(compare(&old, &same), "answer unchanged; 5.000 ms (-5.000 ms, -50%)");
+        assert_eq!(compare(&old, &new), "answer changed from \"42\"; 20.000 ms (+10.000 ms, +100%)");
+    }
+}
+
+/// Modification time of every watched file, `None` if it doesn't exist
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// The day's input and source, plus every `dayN_test*` file in the input directory
+struct WatchedFiles {
+    fixed: Vec<PathBuf>,
+    test_dir: PathBuf,
+    test_prefix: String
+}
+
+impl WatchedFiles {
+    fn snapshot(&self) -> Snapshot {
+        let mut paths = self.fixed.clone();
+        // listed every time so that newly added examples are picked up too
+        if let Ok(entries) = fs::read_dir(&self.test_dir) {
+            let mut tests: Vec<PathBuf> = entries
+                .filter_map(| entry | entry.ok())
+                .map(| entry | entry.path())
+                .filter(| path | path.file_name().is_some_and(| name | name.to_string_lossy().starts_with(&self.test_prefix)))
+                .collect();
+            tests.sort();
+            // `--input` may point at one of the examples
+            let fixed: Vec<PathBuf> = self.fixed.iter().filter_map(| path | fs::canonicalize(path).ok()).collect();
+            tests.retain(| path | fs::canonicalize(path).map_or(true, | path | !fixed.contains(&path)));
+            paths.extend(tests);
+        }
+        paths
+            .into_iter()
+            .map(
+                | path | {
+                    let modified = fs::metadata(&path).and_then(| meta | meta.modified()).ok();
+                    (path, modified)
+                }
+            )
+            .collect()
+    }
+}
+
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(| entry | !before.contains(entry))
+        .map(| (path, _) | path.clone())
+        .collect();
+    for (path, _) in before {
+        if !after.iter().any(| (p, _) | p == path) {
+            changed.push(path.clone());
+        }
+    }
+    changed
+}
+
+/// One run's answer and time, read back from the runner's JSON output
+#[derive(Debug)]
+struct RunResult {
+    answer: String,
+    millis: f64
+}
+
+impl RunResult {
+    /// Picks `answer` and `ms` out of a line like the ones `--output json` prints
+    fn from_json(line: &str) -> Option<Self> {
+        let answer_start = line.find("\"answer\": \"")? + "\"answer\": \"".len();
+        let mut answer = String::new();
+        let mut chars = line[answer_start..].chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '"' => break,
+                '\\' => match chars.next()? {
+                    'n' => answer.push('\n'),
+                    'u' => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        answer.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    },
+                    other => answer.push(other)
+                },
+                ch => answer.push(ch)
+            }
+        }
+        let millis_start = line.find("\"ms\": ")? + "\"ms\": ".len();
+        let millis = line[millis_start..].trim_end_matches('}').trim().parse().ok()?;
+        Some(Self { answer, millis })
+    }
+}
+
+fn compare(previous: &RunResult, current: &RunResult) -> String {
+    let answer = if previous.answer == current.answer {
+        String::from("answer unchanged")
+    } else {
+        format!("answer changed from {:?}", previous.answer)
+    };
+    let delta = current.millis - previous.millis;
+    let percent = if previous.millis > 0.0 { delta / previous.millis * 100.0 } else { 0.0 };
+    format!("{answer}; {:.3} ms ({delta:+.3} ms, {percent:+.0}%)", current.millis)
+}
+
+/// `watch <day>-<part> [options]` polls the day's input, examples and source, and reruns the solver
+/// and the day's tests whenever one changes. Runs go through `cargo` so that source edits are rebuilt.
+/// Options other than `--interval-ms` are passed on to the runner.
+pub fn watch_command(args: &[String]) {
+    let Some((year, day, part)) = args.first().and_then(| problem | crate::runner::parse_problem(problem)) else {
+        println!("Usage: watch <day>-<part> [--interval-ms N] [runner options]");
+        return;
+    };
+    let interval = Duration::from_millis(crate::flag_value(args, "--interval-ms").unwrap_or(500));
+    let mut runner_args: Vec<String> = Vec::new();
+    let mut options = args[1..].iter();
+    while let Some(option) = options.next() {
+        let value = options.next().cloned().unwrap_or_default();
+        if option != "--interval-ms" {
+            runner_args.extend([option.clone(), value]);
+        }
+    }
+
+    let settings = match crate::split_options(&runner_args) {
+        Ok((_, cli, config_path)) => match crate::config::Config::load(&config_path) {
+            Ok(config) => config.settings(year, day, &cli),
+            Err(err) => {
+                println!("Bad config file {}: {err}", config_path.display());
+                return;
+            }
+        },
+        Err(err) => {
+            println!("{err}");
+            return;
+        }
+    };
+    let input_dir = settings.input_dir.join(year.to_string());
+    let input = settings.input.unwrap_or_else(|| input_dir.join(format!("day{day}.txt")));
+    let source = PathBuf::from(format!("src/year{year}/day{day}.rs"));
+    let files = WatchedFiles { fixed: vec![input, source], test_dir: input_dir, test_prefix: format!("day{day}_test") };
+
+    let problem = format!("{year}-{day}-{part}");
+    println!("Watching {problem}; press Ctrl-C to stop");
+    let mut previous: Option<RunResult> = None;
+    let mut snapshot = files.snapshot();
+    loop {
+        let current = rerun(&problem, year, day, &runner_args);
+        match (&previous, &current) {
+            (Some(previous), Some(current)) => println!("{}\n{}", current.answer, compare(previous, current)),
+            (None, Some(current)) => println!("{}\n{:.3} ms", current.answer, current.millis),
+            (_, None) => println!("No answer this time")
+        }
+        if current.is_some() {
+            previous = current;
+        }
+
+        let changed = loop {
+            thread::sleep(interval);
+            let next = files.snapshot();
+            let changed = changed_files(&snapshot, &next);
+            snapshot = next;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        let names: Vec<String> = changed.iter().map(| path | path.display().to_string()).collect();
+        println!("\nChanged: {}", names.join(", "));
+    }
+}
+
+/// Runs the problem and then the day's tests, returning the problem's result if it produced one
+fn rerun(problem: &str, year: u32, day: u32, runner_args: &[String]) -> Option<RunResult> {
+    let cargo = env::var("CARGO").unwrap_or_else(| _ | String::from("cargo"));
+    let output = Command::new(&cargo)
+        .args(["run", "--quiet", "--", problem, "--output", "json"])
+        .args(runner_args)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result = stdout.lines().find_map(RunResult::from_json);
+    if result.is_none() {
+        // compile errors and panics land on stderr
+        print!("{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+    }
+
+    let tests = Command::new(&cargo)
+        .args(["test", "--quiet", &format!("year{year}::day{day}::")])
+        .output();
+    if let Ok(tests) = tests {
+        let stdout = String::from_utf8_lossy(&tests.stdout);
+        let summary = stdout.lines().rev().find(| line | line.starts_with("test result"));
+        println!("tests: {}", summary.unwrap_or("did not run"));
+        if !tests.status.success() {
+            print!("{stdout}");
+        }
+    }
+
+    result
+}
+
@@ -0,0 +1,418 @@
+use crate::automaton::AhoCorasick;
+use crate::{options, trace, utilities};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_data_1() {
+        let output = part1("./input/2023/day1_test1.txt");
+        assert_eq!(output, "Sum: 142");
+    }
+
+    #[test]
+    fn test_data_2() {
+        let output = part2("./input/2023/day1_test2.txt");
+        assert_eq!(output, "281");
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let matcher = DigitMatcher::from_names("english");
+        let first = matcher.first("xeightwo").unwrap();
+        let last = matcher.last("xeightwo").unwrap();
+        assert_eq!((first.value, first.start, first.end), (8, 1, 6));
+        assert_eq!((last.value, last.start, last.end), (2, 5, 8));
+        assert!(matcher.first("abc").is_none());
+    }
+
+    #[test]
+    fn bad_lines_are_reported_not_fatal() {
+        let matcher = DigitMatcher::from_names("english");
+        let lines: Vec<String> = ["two1nine", "nothing here", "abcone2threexyz", "", "7pqrstsixteen"]
+            .iter()
+            .map(| line | String::from(*line))
+            .collect();
+        let (total, errors) = sum_calibration(&lines, &matcher, 1);
+        assert_eq!(total, 29 + 13 + 76);
+        let numbers: Vec<usize> = errors.iter().map(| error | error.line_number).collect();
+        assert_eq!(numbers, vec![2, 4]);
+        assert_eq!(errors[0].line, "nothing here");
+    }
+
+    #[test]
+    fn batches_agree_with_serial() {
+        let mut lines: Vec<String> = crate::generate::generate(1, 7, 3 * BATCH_SIZE + 17).unwrap().lines().map(String::from).collect();
+        lines[BATCH_SIZE + 5] = String::from("no numbers");
+        let matcher = DigitMatcher::from_names("english");
+        let serial = sum_calibration(&lines, &matcher, 1);
+        assert_eq!(sum_calibration(&lines, &matcher, 3), serial);
+        assert_eq!(serial.1[0].line_number, BATCH_SIZE + 6);
+    }
+
+    /// `cargo test --release day1 -- --ignored --nocapture` times the serial part 1 scan and part 2
+    /// at several thread counts on a generated input of a few megabytes
+    #[test]
+    #[ignore]
+    fn benchmark_threads() {
+        let input = crate::generate::generate(1, 1, 400_000).unwrap();
+        let path = std::env::temp_dir().join("aoc2023_day1_bench.txt");
+        std::fs::write(&path, &input).unwrap();
+        let path = path.to_string_lossy().into_owned();
+        println!("{} bytes, {} lines", input.len(), input.lines().count());
+
+        let start = std::time::Instant::now();
+        part1(&path);
+        println!("part 1, serial: {:?}", start.elapsed());
+        let mut answers = Vec::new();
+        for threads in [1, 2, 4, 8] {
+            options::set(options::PuzzleOptions::from([(String::from("threads"), threads.to_string())]));
+            let start = std::time::Instant::now();
+            answers.push(part2(&path));
+            println!("part 2, {threads} threads: {:?}", start.elapsed());
+        }
+        answers.dedup();
+        assert_eq!(answers.len(), 1);
+    }
+
+    #[test]
+    fn audit_explains_each_line() {
+        let matcher = DigitMatcher::from_names("english");
+        let digits = DigitMatcher::new(&[]);
+        let overlapping = AuditLine::new("xeightwo", &matcher, &digits);
+        assert_eq!(overlapping.value, Some(82));
+        assert_eq!(overlapping.without_overlaps, Some(88));
+        assert_eq!(overlapping.digits_value, None);
+        assert_eq!(overlapping.notes(), vec!["no digit, so part 1 panics", "overlapping words, 88 without them"]);
+        assert!(AuditLine::new("two1nine", &matcher, &digits).notes().is_empty());
+
+        let lines: Vec<String> = ["two1nine", "eightwothree", "oneight", "abc"].iter().map(| line | String::from(*line)).collect();
+        let report = audit_report(&lines, &matcher, true);
+        let rows: Vec<&str> = report.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows[1].contains("eight@0..5") && rows[1].contains("three@7..12"));
+        assert!(rows[2].contains("one@0..3") && rows[2].contains("eight@2..7") && rows[2].contains("11 without them"));
+        assert!(rows[3].contains("no number"));
+        assert!(report.ends_with("3 of 4 lines flagged, sum 130"));
+    }
+
+    #[test]
+    fn other_vocabularies() {
+        let roman = DigitMatcher::from_names("roman");
+        assert_eq!(roman.calibration_value("axivbviiic"), Some(148));
+        let teens = DigitMatcher::from_names("english,teens");
+        assert_eq!(teens.calibration_value("sixteenabcnineteen"), Some(1619));
+        assert_eq!(teens.calibration_value("nine"), Some(99));
+        let german = DigitMatcher::from_names("german");
+        assert_eq!(german.calibration_value("xdreiundfünfzig"), Some(35));
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let mut lines = utilities::lines_from_file(path);
+    let mut total:u32 = 0;
+    while let Some(Ok(line)) = lines.next() {
+        // println!("{}", &line);
+        let chars = line.chars();
+        let rev_chars = line.chars().rev();
+        let first_num = first_number(chars).expect("Couldn't find a first number");
+        let last_num = first_number(rev_chars).expect("Couldn't find a last number");
+        total += first_num*10 + last_num;
+    }
+    
+    format!("Sum: {total}")
+}
+
+fn first_number(chars: impl Iterator<Item = char>) -> Option<u32> {
+    for ch in chars {
+        if let Some(num) = ch.to_digit(10) {
+            return Some(num);
+        }
+    }
+    return None;
+}
+
+pub fn part2(path: &str) -> String {
+    let lines: Vec<String> = utilities::lines_from_file(path).map_while(Result::ok).collect();
+    let vocabulary: String = options::get_or("vocabulary", String::from("english"));
+    let matcher = DigitMatcher::from_names(&vocabulary);
+    if let Some(mode) = options::get("audit") {
+        let only_flagged = match mode.as_str() {
+            "all" => false,
+            "flagged" => true,
+            _ => panic!("Bad value {mode:?} for puzzle option audit, expected all or flagged")
+        };
+        return audit_report(&lines, &matcher, only_flagged);
+    }
+    let default_threads = thread::available_parallelism().map_or(1, | threads | threads.get());
+    let threads: usize = options::get_or("threads", default_threads).max(1);
+    let (total, errors) = sum_calibration(&lines, &matcher, threads);
+    if errors.is_empty() {
+        return format!("{total}");
+    }
+
+    for error in &errors {
+        trace::debug(|| format!("line {} has no number: {:?}", error.line_number, error.line));
+    }
+    let mut listed: Vec<String> = errors.iter().take(10).map(| error | error.line_number.to_string()).collect();
+    if errors.len() > listed.len() {
+        listed.push(String::from("..."));
+    }
+    format!("{total} (skipped {} lines without a number: {})", errors.len(), listed.join(", "))
+}
+
+/// How many lines a worker takes at a time
+const BATCH_SIZE: usize = 4096;
+
+/// A line with no number in it, numbered from 1
+#[derive(Debug, PartialEq, Eq)]
+pub struct LineError {
+    pub line_number: usize,
+    pub line: String
+}
+
+/// Sums the calibration values of `lines`, skipping and returning those without a number.
+/// With more than one thread, workers take batches of lines off a shared counter.
+fn sum_calibration(lines: &[String], matcher: &DigitMatcher, threads: usize) -> (u64, Vec<LineError>) {
+    let sum_batch = | first: usize, batch: &[String] | {
+        let mut total = 0;
+        let mut errors = Vec::new();
+        for (i, line) in batch.iter().enumerate() {
+            match matcher.calibration_value(line) {
+                Some(value) => total += value,
+                None => errors.push(LineError { line_number: first + i + 1, line: line.clone() })
+            }
+        }
+        (total, errors)
+    };
+    if threads == 1 {
+        return sum_batch(0, lines);
+    }
+
+    let next_batch = AtomicUsize::new(0);
+    let mut total = 0;
+    let mut errors = Vec::new();
+    thread::scope(
+        | scope | {
+            let workers: Vec<_> = (0..threads.min(lines.len().div_ceil(BATCH_SIZE)))
+                .map(
+                    | _ | scope.spawn(
+                        || {
+                            let mut total = 0;
+                            let mut errors = Vec::new();
+                            loop {
+                                let first = next_batch.fetch_add(1, Ordering::Relaxed) * BATCH_SIZE;
+                                let Some(batch) = lines.get(first..(first + BATCH_SIZE).min(lines.len())) else { break };
+                                if batch.is_empty() {
+                                    break;
+                                }
+                                let (batch_total, batch_errors) = sum_batch(first, batch);
+                                total += batch_total;
+                                errors.extend(batch_errors);
+                            }
+                            (total, errors)
+                        }
+                    )
+                )
+                .collect();
+            for worker in workers {
+                let (worker_total, worker_errors) = worker.join().expect("Calibration worker panicked");
+                total += worker_total;
+                errors.extend(worker_errors);
+            }
+        }
+    );
+    errors.sort_by_key(| error | error.line_number);
+    (total, errors)
+}
+
+/// Writes `last` after `first`, so two digits make a two digit number and teens make four
+fn join_values(first: u32, last: u32) -> u64 {
+    let mut shift = 10;
+    while shift <= u64::from(last) {
+        shift *= 10;
+    }
+    u64::from(first) * shift + u64::from(last)
+}
+
+/// Spelled out numbers by vocabulary name, as accepted by `--puzzle-vocabulary`
+fn vocabulary(name: &str) -> Option<Vec<(String, u32)>> {
+    let words: &[&str] = match name {
+        "english" => &["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"],
+        "german" => &["null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"],
+        "french" => &["zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"],
+        "spanish" => &["cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"],
+        "teens" => {
+            let teens = ["ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen"];
+            return Some(teens.iter().map(| word | String::from(*word)).zip(10..).collect());
+        },
+        "roman" => return Some((1..=39).map(| value | (roman_numeral(value), value)).collect()),
+        _ => return None
+    };
+    Some(words.iter().map(| word | String::from(*word)).zip(0..).collect())
+}
+
+fn roman_numeral(mut value: u32) -> String {
+    let mut out = String::new();
+    for (symbol, size) in [("x", 10), ("ix", 9), ("v", 5), ("iv", 4), ("i", 1)] {
+        while value >= size {
+            out.push_str(symbol);
+            value -= size;
+        }
+    }
+    out
+}
+
+/// A number found in a line, with its byte range
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub value: u32,
+    pub start: usize,
+    pub end: usize
+}
+
+/// Finds the first and last number in a line, whether a digit or a word from its vocabulary.
+/// Words may overlap, so `eightwo` starts with 8 and ends with 2. Matching ignores ASCII case.
+pub struct DigitMatcher {
+    values: Vec<u32>,
+    forward: AhoCorasick,
+    /// The same patterns spelled backwards, run over the line from its end
+    reverse: AhoCorasick
+}
+
+impl DigitMatcher {
+    /// Matches the digits 0 to 9 and the given words
+    pub fn new(words: &[(String, u32)]) -> Self {
+        let mut patterns: Vec<String> = (0..10).map(| digit: u32 | digit.to_string()).collect();
+        let mut values: Vec<u32> = (0..10).collect();
+        for (word, value) in words {
+            patterns.push(word.to_lowercase());
+            values.push(*value);
+        }
+        let reversed: Vec<Vec<u8>> = patterns.iter().map(| pattern | pattern.bytes().rev().collect()).collect();
+        Self { values, forward: AhoCorasick::new(&patterns), reverse: AhoCorasick::new(&reversed) }
+    }
+
+    /// Comma separated vocabulary names, such as `english,teens`. Panics on an unknown name.
+    pub fn from_names(names: &str) -> Self {
+        let mut words = Vec::new();
+        for name in names.split(',').map(str::trim).filter(| name | !name.is_empty()) {
+            words.extend(vocabulary(name).unwrap_or_else(|| panic!("Unknown vocabulary {name:?}")));
+        }
+        Self::new(&words)
+    }
+
+    /// The number starting earliest, taking the longest word if more than one starts there
+    pub fn first(&self, line: &str) -> Option<Token> {
+        self.first_in(line.to_ascii_lowercase().as_bytes())
+    }
+
+    /// The number ending last, taking the longest word if more than one ends there
+    pub fn last(&self, line: &str) -> Option<Token> {
+        self.last_in(line.to_ascii_lowercase().as_bytes())
+    }
+
+    fn first_in(&self, lowercase: &[u8]) -> Option<Token> {
+        let found = self.forward.leftmost(lowercase)?;
+        Some(Token { value: self.values[found.pattern], start: found.start, end: found.end })
+    }
+
+    fn last_in(&self, lowercase: &[u8]) -> Option<Token> {
+        let reversed: Vec<u8> = lowercase.iter().rev().copied().collect();
+        let found = self.reverse.leftmost(&reversed)?;
+        Some(Token { value: self.values[found.pattern], start: lowercase.len() - found.end, end: lowercase.len() - found.start })
+    }
+
+    /// The first and last numbers written one after the other
+    pub fn calibration_value(&self, line: &str) -> Option<u64> {
+        let lowercase = line.to_ascii_lowercase();
+        Some(join_values(self.first_in(lowercase.as_bytes())?.value, self.last_in(lowercase.as_bytes())?.value))
+    }
+}
+
+/// How one line was read, for the audit report
+#[derive(Debug, PartialEq, Eq)]
+struct AuditLine {
+    first: Option<Token>,
+    last: Option<Token>,
+    value: Option<u64>,
+    /// Part 1's value, from digits alone
+    digits_value: Option<u64>,
+    /// The value if each word used up its letters, reading left to right
+    without_overlaps: Option<u64>
+}
+
+impl AuditLine {
+    fn new(line: &str, matcher: &DigitMatcher, digits: &DigitMatcher) -> Self {
+        let first = matcher.first(line);
+        let last = matcher.last(line);
+        let value = first.zip(last).map(| (first, last) | join_values(first.value, last.value));
+
+        let lowercase = line.to_ascii_lowercase();
+        let mut position = 0;
+        let mut consumed: Vec<Token> = Vec::new();
+        while let Some(token) = matcher.first_in(&lowercase.as_bytes()[position..]) {
+            position += token.end;
+            consumed.push(token);
+        }
+        let without_overlaps = consumed.first().zip(consumed.last()).map(| (first, last) | join_values(first.value, last.value));
+
+        Self { first, last, value, digits_value: digits.calibration_value(line), without_overlaps }
+    }
+
+    fn notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+        if self.value.is_none() {
+            notes.push(String::from("no number"));
+        }
+        if self.digits_value.is_none() {
+            notes.push(String::from("no digit, so part 1 panics"));
+        }
+        if self.value != self.without_overlaps {
+            let without = self.without_overlaps.map_or(String::from("-"), | value | value.to_string());
+            notes.push(format!("overlapping words, {without} without them"));
+        }
+        notes
+    }
+}
+
+/// Lists the tokens behind each line's value, as `text@start..end` byte ranges.
+/// Lines whose value depends on overlapping words, or that have no number or no digit, get notes.
+fn audit_report(lines: &[String], matcher: &DigitMatcher, only_flagged: bool) -> String {
+    let digits = DigitMatcher::new(&[]);
+    let token_text = | line: &str, token: Option<Token> | match token {
+        Some(token) => format!("{}@{}..{}", &line[token.start..token.end], token.start, token.end),
+        None => String::from("-")
+    };
+    let number = | value: Option<u64> | value.map_or(String::from("-"), | value | value.to_string());
+
+    let mut out = format!("{:>6}  {:>6}  {:<16}{:<16}{:>6}  notes\n", "line", "value", "first", "last", "part 1");
+    let mut flagged = 0;
+    let mut total = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let audit = AuditLine::new(line, matcher, &digits);
+        total += audit.value.unwrap_or(0);
+        let notes = audit.notes();
+        if !notes.is_empty() {
+            flagged += 1;
+        } else if only_flagged {
+            continue;
+        }
+        let row = format!(
+            "{:>6}  {:>6}  {:<16}{:<16}{:>6}  {}",
+            i + 1,
+            number(audit.value),
+            token_text(line, audit.first),
+            token_text(line, audit.last),
+            number(audit.digits_value),
+            notes.join("; ")
+        );
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out.push_str(&format!("{flagged} of {} lines flagged, sum {total}", lines.len()));
+    out
+}
@@ -8,43 +8,43 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day10_test1.txt");
+        let result = part1("./input/2023/day10_test1.txt");
         assert_eq!(result, "The most distance point is 4 steps")
     }
 
     #[test]
     fn part1_test2() {
-        let result = part1("./input/day10_test2.txt");
+        let result = part1("./input/2023/day10_test2.txt");
         assert_eq!(result, "The most distance point is 8 steps")
     }
 
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day10_test1.txt");
+        let result = part2("./input/2023/day10_test1.txt");
         assert_eq!(result, "There are 1 enclosed squares")
     }
 
     #[test]
     fn part2_test2() {
-        let result = part2("./input/day10_test2.txt");
+        let result = part2("./input/2023/day10_test2.txt");
         assert_eq!(result, "There are 1 enclosed squares")
     }
 
     #[test]
     fn part2_test3() {
-        let result = part2("./input/day10_test3.txt");
+        let result = part2("./input/2023/day10_test3.txt");
         assert_eq!(result, "There are 4 enclosed squares")
     }
 
     #[test]
     fn part2_test4() {
-        let result = part2("./input/day10_test4.txt");
+        let result = part2("./input/2023/day10_test4.txt");
         assert_eq!(result, "There are 8 enclosed squares")
     }
 
     #[test]
     fn part2_test5() {
-        let result = part2("./input/day10_test5.txt");
+        let result = part2("./input/2023/day10_test5.txt");
         assert_eq!(result, "There are 10 enclosed squares")
     }
 
@@ -5,13 +5,22 @@ use crate::utilities;
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::snapshot;
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day11_test1.txt");
+        let result = part1("./input/2023/day11_test1.txt");
 
         assert_eq!(result, "The sum of shortest paths is 374")
     }
+
+    #[test]
+    fn universe_snapshot() {
+        let lines = utilities::string_iterator("./input/2023/day11_test1.txt");
+        let universe = Universe::read(lines, 2);
+
+        snapshot::assert_snapshot("day11_universe", &universe.to_string());
+    }
 }
 
 pub fn part1(path: &str) -> String {
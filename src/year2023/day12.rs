@@ -6,20 +6,81 @@ use crate::utilities;
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::property::{self, Arbitrary};
+    use crate::rng::Rng;
 
     #[test]
     fn part1_test_1() {
-        let result = part1("./input/day12_test1.txt");
+        let result = part1("./input/2023/day12_test1.txt");
 
         assert_eq!(result, "The total number of possibilities is 21")
     }
 
     #[test]
     fn part2_test_1() {
-        let result = part2("./input/day12_test1.txt");
+        let result = part2("./input/2023/day12_test1.txt");
 
         assert_eq!(result, "The total number of possibilities is 525152")
     }
+
+    /// A fully known row with some springs hidden, so the block list always has a solution
+    #[derive(Clone, Debug)]
+    struct SpringRow {
+        broken: Vec<bool>,
+        hidden: Vec<bool>
+    }
+
+    impl SpringRow {
+        fn line(&self) -> String {
+            let springs: String = self.broken.iter()
+                .zip(self.hidden.iter())
+                .map(| (broken, hidden) | if *hidden {'?'} else if *broken {'#'} else {'.'})
+                .collect();
+            let blocks: Vec<String> = self.broken
+                .split(| broken | !*broken)
+                .filter(| block | !block.is_empty())
+                .map(| block | block.len().to_string())
+                .collect();
+            format!("{springs} {}", blocks.join(","))
+        }
+    }
+
+    impl Arbitrary for SpringRow {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            let len = rng.range(1..size + 2);
+            let mut broken: Vec<bool> = (0..len).map(| _ | rng.chance(0.4)).collect();
+            let index = rng.range(0..len);
+            broken[index] = true;
+            let hidden = (0..len).map(| _ | rng.chance(0.5)).collect();
+            Self { broken, hidden }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            for i in 0..self.broken.len() {
+                let mut shorter = self.clone();
+                shorter.broken.remove(i);
+                shorter.hidden.remove(i);
+                if shorter.broken.contains(&true) {
+                    smaller.push(shorter);
+                }
+                if self.hidden[i] {
+                    let mut revealed = self.clone();
+                    revealed.hidden[i] = false;
+                    smaller.push(revealed);
+                }
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn naive_matches_memoized() {
+        property::assert_property(12, 200, 14, | row: &SpringRow | {
+            let fixer = Fixer::read(row.line());
+            property::agree(&fixer.find_fixes_naive(), &fixer.find_fixes())
+        });
+    }
 }
 
 pub fn part1(path: &str) -> String {
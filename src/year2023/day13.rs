@@ -6,14 +6,14 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day13_test1.txt");
+        let result = part1("./input/2023/day13_test1.txt");
 
         assert_eq!(result, "The sum is 405");
     }
 
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day13_test1.txt");
+        let result = part2("./input/2023/day13_test1.txt");
 
         assert_eq!(result, "The sum is 400");
     }
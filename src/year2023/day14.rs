@@ -4,17 +4,18 @@ use crate::utilities;
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::snapshot;
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day14_test1.txt");
+        let result = part1("./input/2023/day14_test1.txt");
 
         assert_eq!(result, "The total load is 136")
     }
 
     #[test]
     fn part2_test1() {
-        let lines = utilities::string_iterator("./input/day14_test1.txt");
+        let lines = utilities::string_iterator("./input/2023/day14_test1.txt");
         let mut platform = PlatformFull::read(lines);
         platform.roll_up();
         let total = platform.calculate_load();
@@ -23,16 +24,24 @@ mod testing {
 
     // #[test]
     // fn part2_test2() {
-    //     let lines = utilities::string_iterator("./input/day14_test1.txt");
+    //     let lines = utilities::string_iterator("./input/2023/day14_test1.txt");
     //     let mut platform = PlatformFull::read(lines);
     //     platform.cycle(3);
     //     let answer = ".....#....\n....#...O#\n.....##...\n..O#......\n.....OOO#.\n.O#...O#.#\n....O#...O\n.......OOO\n#...O###.O\n#.OOO#...O\n";
     //     assert_eq!(answer, platform.as_string());
     // }
 
+    #[test]
+    fn one_spin_cycle_snapshot() {
+        let lines = utilities::string_iterator("./input/2023/day14_test1.txt");
+        let mut platform = PlatformFull::read(lines);
+        platform.spin_cycle();
+        snapshot::assert_snapshot("day14_one_spin_cycle", &platform.as_string());
+    }
+
     #[test]
     fn part2_test3() {
-        let result = part2("./input/day14_test1.txt");
+        let result = part2("./input/2023/day14_test1.txt");
 
         assert_eq!(result, "The total load is 64")
     }
@@ -216,6 +225,14 @@ impl PlatformFull {
         }
     }
 
+    /// Rolls north, west, south, then east
+    fn spin_cycle(&mut self) {
+        self.roll_up();
+        self.roll_left();
+        self.roll_down();
+        self.roll_right();
+    }
+
     fn roller_indices(&self) -> Vec<usize> {
         self.array.iter().enumerate().filter(|(_, e)| e.is_roller()).map(|(i, _)| i).collect()
     }
@@ -253,10 +270,7 @@ impl PlatformFull {
         while !history.contains(&working_copy.roller_indices()) {
             history.push(working_copy.roller_indices());
             loads.push(working_copy.calculate_load());
-            working_copy.roll_up();
-            working_copy.roll_left();
-            working_copy.roll_down();
-            working_copy.roll_right();
+            working_copy.spin_cycle();
         }
         let cycles_completed = history.len();
         let cycles_remaining = times - cycles_completed;
@@ -313,27 +327,27 @@ impl PlatformFull {
             )
     }
 
-    // fn as_string(&self) -> String {
-    //     let mut output = String::new();
-
-    //     for (i, element) in self.array.iter().enumerate() {
-    //         output.push (
-    //             if element.is_roller() {
-    //                 'O'
-    //             } else if element.is_empty() {
-    //                 '.'
-    //             } else {
-    //                 '#'
-    //             }
-    //         );
-    //         if (i+1) % self.ncols == 0 {
-    //             // println!("{line}");
-    //             output.push('\n');
-    //         }
-    //     }
+    #[cfg(test)]
+    fn as_string(&self) -> String {
+        let mut output = String::new();
+
+        for (i, element) in self.array.iter().enumerate() {
+            output.push (
+                if element.is_roller() {
+                    'O'
+                } else if element.is_empty() {
+                    '.'
+                } else {
+                    '#'
+                }
+            );
+            if (i+1) % self.ncols == 0 {
+                output.push('\n');
+            }
+        }
 
-    //     output
-    // }
+        output
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -6,14 +6,14 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day15_test1.txt");
+        let result = part1("./input/2023/day15_test1.txt");
 
         assert_eq!(result, "The total hash value is 1320")
     }
 
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day15_test1.txt");
+        let result = part2("./input/2023/day15_test1.txt");
 
         assert_eq!(result, "The total focal power is 145")
     }
@@ -6,14 +6,14 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day16_test1.txt");
+        let result = part1("./input/2023/day16_test1.txt");
 
         assert_eq!(result, "The number of energized tiles is 46");
     }
 
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day16_test1.txt");
+        let result = part2("./input/2023/day16_test1.txt");
 
         assert_eq!(result, "The max number of energized tiles is 51");
     }
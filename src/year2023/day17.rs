@@ -8,21 +8,21 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day17_test1.txt");
+        let result = part1("./input/2023/day17_test1.txt");
 
         assert_eq!(result, "The minimum heat loss is 102")
     }
 
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day17_test1.txt");
+        let result = part2("./input/2023/day17_test1.txt");
 
         assert_eq!(result, "The minimum heat loss is 94")
     }
 
     #[test]
     fn part2_test2() {
-        let result = part2("./input/day17_test2.txt");
+        let result = part2("./input/2023/day17_test2.txt");
 
         assert_eq!(result, "The minimum heat loss is 71")
     }
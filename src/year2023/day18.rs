@@ -3,17 +3,21 @@ use crate::utilities;
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::generate::{self, CellTree};
+    use crate::property::{self, Arbitrary};
+    use crate::rng::Rng;
+    use crate::snapshot;
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day18_test1.txt");
+        let result = part1("./input/2023/day18_test1.txt");
 
         assert_eq!(result, "The area dug is 62")
     }
 
     #[test]
     fn part1_test2() {
-        let lines = utilities::string_iterator("./input/day18_test1.txt");
+        let lines = utilities::string_iterator("./input/2023/day18_test1.txt");
         let mut outline = OutlineMap::read(lines);
         outline.draw_map();
 
@@ -21,19 +25,103 @@ mod testing {
         assert_eq!(output, "#######\n#.....#\n###...#\n..#...#\n..#...#\n###.###\n#...#..\n##..###\n.#....#\n.######\n")
     }
 
+    #[test]
+    fn drawn_map_snapshot() {
+        let input = generate::generate(18, 3, 8).unwrap();
+        let mut outline = OutlineMap::read(input.lines().map(String::from));
+        outline.draw_map();
+
+        snapshot::assert_snapshot("day18_generated_outline", &outline.str_out());
+    }
+
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day18_test1.txt");
+        let result = part2("./input/2023/day18_test1.txt");
 
         assert_eq!(result, "The area dug is 952408144115")
     }
 
     #[test]
     fn part2_test2() {
-        let result = part2("./input/day18_test2.txt");
+        let result = part2("./input/2023/day18_test2.txt");
 
         assert_eq!(result, "The area dug is 62")
     }
+
+    /// A loop traced around a tree of cells, with every row and column stretched.
+    /// Rows and columns are at least 2 wide so that, as in the real input, the trench never runs
+    /// alongside itself. The colour codes spell out the same instructions as the plain ones.
+    #[derive(Clone, Debug)]
+    struct DigPlan {
+        tree: CellTree,
+        row_heights: Vec<usize>,
+        col_widths: Vec<usize>
+    }
+
+    impl DigPlan {
+        fn lines(&self) -> Vec<String> {
+            let positions = | steps: &[usize] | {
+                steps.iter().fold(vec![0usize], | mut acc, step | {
+                    acc.push(acc.last().unwrap() + step);
+                    acc
+                })
+            };
+            let runs = generate::loop_runs(&self.tree.outline(), &positions(&self.row_heights), &positions(&self.col_widths));
+            runs.into_iter()
+                .map(| (dir, dist) | {
+                    let code = match dir {
+                        'R' => 0,
+                        'D' => 1,
+                        'L' => 2,
+                        _ => 3
+                    };
+                    format!("{dir} {dist} (#{dist:05x}{code})")
+                })
+                .collect()
+        }
+    }
+
+    impl Arbitrary for DigPlan {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            let side = rng.range(1..size.max(1) + 1);
+            let tree = CellTree::random(rng, side, side);
+            let row_heights = (0..2 * side).map(| _ | rng.range(2..5)).collect();
+            let col_widths = (0..2 * side).map(| _ | rng.range(2..5)).collect();
+            Self { tree, row_heights, col_widths }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller: Vec<Self> = self.tree
+                .without_leaves()
+                .into_iter()
+                .map(| tree | Self { tree, ..self.clone() })
+                .collect();
+            for i in 0..self.row_heights.len() {
+                if self.row_heights[i] > 2 {
+                    let mut flatter = self.clone();
+                    flatter.row_heights[i] = 2;
+                    smaller.push(flatter);
+                }
+                if self.col_widths[i] > 2 {
+                    let mut narrower = self.clone();
+                    narrower.col_widths[i] = 2;
+                    smaller.push(narrower);
+                }
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn grid_count_matches_span_count() {
+        property::assert_property(18, 100, 6, | plan: &DigPlan | {
+            let mut outline = OutlineMap::read(plan.lines().into_iter());
+            outline.draw_map();
+            let mut hex_outline = HexOutlineMap::read(plan.lines().into_iter());
+            hex_outline.construct_map();
+            property::agree(&(outline.count_interior() as u64), &hex_outline.count_interior())
+        });
+    }
 }
 
 pub fn part1(path: &str) -> String {
@@ -8,14 +8,14 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day19_test1.txt");
+        let result = part1("./input/2023/day19_test1.txt");
 
         assert_eq!(result, "The sum of ratings is 19114");
     }
 
     #[test]
     fn part2_test1() {
-        let result = part2("./input/day19_test1.txt");
+        let result = part2("./input/2023/day19_test1.txt");
 
         assert_eq!(result, "The total number of possibilities is 167409079868000")
     }
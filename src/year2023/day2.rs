@@ -0,0 +1,388 @@
+use crate::{options, utilities};
+use std::fmt::Display;
+use std::fs;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_data_1() {
+        let output = part1("./input/2023/day2_test1.txt");
+        assert_eq!(output, "Total of possible game IDs: 8");
+    }
+
+    #[test]
+    fn test_data_2() {
+        let output = part2("./input/2023/day2_test1.txt");
+        assert_eq!(output, "Sum of powers: 2286");
+    }
+
+    #[test]
+    fn any_palette() {
+        let records = vec![
+            GameRecord::parse(String::from("Game 7: 2 teal, 1 dark red; 4 teal")),
+            GameRecord::parse(String::from("Game 9: 3 ochre; 1 dark red, 2 ochre"))
+        ];
+        let bag = Cubes::parse("4 teal, 1 dark red, 2 ochre");
+        assert_eq!(possible_games(&records, &bag), vec![7]);
+        assert_eq!(smallest_bag(&records, &bag).to_string(), "4 teal, 1 dark red, 3 ochre");
+        assert_eq!(records[0].power(&bag), 0);
+        assert_eq!(records[1].power(&Cubes::parse("1 dark red, 1 ochre")), 3);
+
+        let colours: Vec<String> = ('a'..='h').map(|c| format!("20 {c}")).collect();
+        let many = Cubes::parse(&colours.join(", "));
+        let game = GameRecord::parse(format!("Game 1: {}", colours.join(", ")));
+        assert_eq!(game.power(&many), 20u128.pow(8));
+        assert_eq!(Cubes::parse("4294967295 a, 4294967295 b, 4294967295 c, 4294967295 d, 4294967295 e").prod(&many), None);
+    }
+
+    #[test]
+    fn per_game_statistics() {
+        let records = read_records("./input/2023/day2_test1.txt");
+        let bag = Cubes::parse("12 red, 13 green, 14 blue");
+        let game3 = GameStats::new(&records[2], &bag);
+        assert_eq!(game3.minimum.to_string(), "20 red, 13 green, 6 blue");
+        assert_eq!(game3.power, 1560);
+        assert_eq!(game3.infeasible, Some((1, vec![String::from("20 red > 12")])));
+        assert_eq!(GameStats::new(&records[0], &bag).infeasible, None);
+
+        let red = color_stats(&records, "red");
+        assert_eq!((red.draws, red.total, red.min, red.max, red.median), (11, 61, 1, 20, 4.0));
+
+        let csv = report_csv(&[game3], &[red]);
+        assert_eq!(csv, "game,min_red,power,possible,infeasible_draw,exceeded\n3,20,1560,false,1,20 red > 12\n\n\
+                         colour,draws,total,min,max,mean,median\nred,11,61,1,20,5.5455,4");
+    }
+
+    #[test]
+    fn queries() {
+        let path = "./input/2023/day2_test1.txt";
+        options::set(options::PuzzleOptions::from([(String::from("query"), String::from("possible"))]));
+        assert_eq!(part1(path), "Games possible with 12 red, 13 green, 14 blue: 1, 2, 5");
+        options::set(options::PuzzleOptions::from([
+            (String::from("query"), String::from("smallest")),
+            (String::from("games"), String::from("1, 2"))
+        ]));
+        assert_eq!(part2(path), "Smallest bag for games 1, 2: 4 red, 3 green, 6 blue");
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let records = read_records(path);
+    let maximums = bag();
+    if let Some(answer) = report(&records, &maximums).or_else(|| query(&records, &maximums)) {
+        return answer;
+    }
+
+    let total:u32 = records.iter()
+                        .filter(|r| r.possible(&maximums))
+                        .map(|r| r.id)
+                        .sum();
+
+    format!("Total of possible game IDs: {}", total)
+}
+
+pub fn part2(path: &str) -> String {
+    let records = read_records(path);
+    let palette = bag();
+    if let Some(answer) = report(&records, &palette).or_else(|| query(&records, &palette)) {
+        return answer;
+    }
+
+    let total:u128 = records.iter()
+                            .map(|r| r.power(&palette))
+                            .try_fold(0u128, |total, power| total.checked_add(power))
+                            .expect("The sum of powers doesn't fit in 128 bits");
+
+    format!("Sum of powers: {}", total)
+}
+
+fn read_records(path: &str) -> Vec<GameRecord> {
+    let mut records:Vec<GameRecord> = Vec::new();
+    let lines = utilities::lines_from_file(path);
+    for line in lines {
+        if let Ok(line_text) = line {
+            records.push(GameRecord::parse(line_text));
+        } else {
+            panic!("Failed to read a line");
+        }
+    }
+    records
+}
+
+/// The bag from `--puzzle-bag "12 red, 13 green"`, or from the file named by `--puzzle-bag-file`,
+/// written like a draw. Its colours are also the ones part 2 multiplies.
+fn bag() -> Cubes {
+    if let Some(bag) = options::get("bag") {
+        return Cubes::parse(&bag);
+    }
+    if let Some(path) = options::get("bag_file") {
+        let text = fs::read_to_string(&path).unwrap_or_else(|_| panic!("Couldn't read bag file {path}"));
+        return Cubes::parse(text.trim());
+    }
+    Cubes::parse("12 red, 13 green, 14 blue")
+}
+
+/// Answers `--puzzle-query possible` or `--puzzle-query smallest` instead of the usual sum,
+/// over the games listed in `--puzzle-games` (comma separated IDs) or all of them
+fn query(records: &[GameRecord], bag: &Cubes) -> Option<String> {
+    let kind = options::get("query")?;
+    let selected: Vec<&GameRecord> = match options::get("games") {
+        Some(ids) => ids
+            .split(',')
+            .map(|id| {
+                let id: u32 = id.trim().parse().unwrap_or_else(|_| panic!("Bad game ID {id:?}"));
+                records.iter().find(|r| r.id == id).unwrap_or_else(|| panic!("No game {id}"))
+            })
+            .collect(),
+        None => records.iter().collect()
+    };
+    let selected: Vec<GameRecord> = selected.into_iter().cloned().collect();
+    let ids: Vec<String> = selected.iter().map(|r| r.id.to_string()).collect();
+    match kind.as_str() {
+        "possible" => {
+            let possible: Vec<String> = possible_games(&selected, bag).iter().map(|id| id.to_string()).collect();
+            Some(format!("Games possible with {bag}: {}", possible.join(", ")))
+        },
+        "smallest" => Some(format!("Smallest bag for games {}: {}", ids.join(", "), smallest_bag(&selected, bag))),
+        _ => panic!("Unknown query {kind:?}, expected possible or smallest")
+    }
+}
+
+/// What one game needed, and whether the bag allowed it
+struct GameStats {
+    id: u32,
+    minimum: Cubes,
+    power: u128,
+    /// The first draw (numbered from 1) with more of some colour than the bag holds, and those colours
+    infeasible: Option<(usize, Vec<String>)>
+}
+
+impl GameStats {
+    fn new(record: &GameRecord, bag: &Cubes) -> Self {
+        let infeasible = record.draws.iter().enumerate().find(|(_, draw)| !draw.possible(bag)).map(|(i, draw)| {
+            let exceeded = draw.counts.iter()
+                .filter(|(color, count)| *count > bag.get(color))
+                .map(|(color, count)| format!("{count} {color} > {}", bag.get(color)))
+                .collect();
+            (i + 1, exceeded)
+        });
+        Self { id: record.id, minimum: smallest_bag(std::slice::from_ref(record), bag), power: record.power(bag), infeasible }
+    }
+}
+
+/// How many of one colour were shown, over the draws that showed it
+#[derive(Debug, PartialEq)]
+struct ColorStats {
+    color: String,
+    draws: usize,
+    total: u64,
+    min: u32,
+    max: u32,
+    mean: f64,
+    median: f64
+}
+
+fn color_stats(records: &[GameRecord], color: &str) -> ColorStats {
+    let mut counts: Vec<u32> = records.iter()
+        .flat_map(|r| r.draws.iter())
+        .filter(|draw| draw.counts.iter().any(|(c, _)| c == color))
+        .map(|draw| draw.get(color))
+        .collect();
+    counts.sort_unstable();
+    let total: u64 = counts.iter().map(|&count| u64::from(count)).sum();
+    let (mean, median) = match counts.len() {
+        0 => (0.0, 0.0),
+        n if n % 2 == 1 => (total as f64 / n as f64, counts[n / 2] as f64),
+        n => (total as f64 / n as f64, (counts[n / 2 - 1] + counts[n / 2]) as f64 / 2.0)
+    };
+    ColorStats {
+        color: String::from(color),
+        draws: counts.len(),
+        total,
+        min: counts.first().copied().unwrap_or(0),
+        max: counts.last().copied().unwrap_or(0),
+        mean,
+        median
+    }
+}
+
+/// `--puzzle-report table` or `--puzzle-report csv` gives each game's minimum bag, power and first
+/// infeasible draw, then each colour's distribution over all draws, instead of the usual sum
+fn report(records: &[GameRecord], bag: &Cubes) -> Option<String> {
+    let format = options::get("report")?;
+    let games: Vec<GameStats> = records.iter().map(|r| GameStats::new(r, bag)).collect();
+    // the bag's colours first, then any others the games used
+    let colors: Vec<String> = smallest_bag(records, bag).counts.into_iter().map(|(color, _)| color).collect();
+    let colors: Vec<ColorStats> = colors.iter().map(|color| color_stats(records, color)).collect();
+    match format.as_str() {
+        "table" => Some(report_table(&games, &colors)),
+        "csv" => Some(report_csv(&games, &colors)),
+        _ => panic!("Unknown report format {format:?}, expected table or csv")
+    }
+}
+
+fn report_table(games: &[GameStats], colors: &[ColorStats]) -> String {
+    let bag_width = games.iter().map(|game| game.minimum.to_string().len()).max().unwrap_or(0).max(11);
+    let mut out = format!("{:>6}  {:<bag_width$}  {:>8}  {:<8}  first infeasible draw\n", "game", "minimum bag", "power", "possible");
+    for game in games {
+        let (possible, draw) = match &game.infeasible {
+            None => ("yes", String::new()),
+            Some((draw, exceeded)) => ("no", format!("draw {draw}: {}", exceeded.join(", ")))
+        };
+        let row = format!("{:>6}  {:<bag_width$}  {:>8}  {possible:<8}  {draw}", game.id, game.minimum.to_string(), game.power);
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+
+    let color_width = colors.iter().map(|stats| stats.color.len()).max().unwrap_or(0).max(6);
+    out.push_str(&format!("\n{:<color_width$}  {:>6}  {:>8}  {:>5}  {:>5}  {:>7}  {:>7}\n", "colour", "draws", "total", "min", "max", "mean", "median"));
+    for stats in colors {
+        out.push_str(&format!(
+            "{:<color_width$}  {:>6}  {:>8}  {:>5}  {:>5}  {:>7.2}  {:>7.1}\n",
+            stats.color, stats.draws, stats.total, stats.min, stats.max, stats.mean, stats.median
+        ));
+    }
+    out.pop();
+    out
+}
+
+/// Two CSV tables separated by a blank line. Colours become `min_<colour>` columns.
+fn report_csv(games: &[GameStats], colors: &[ColorStats]) -> String {
+    let quote = |text: &str| if text.contains([',', '"']) { format!("\"{}\"", text.replace('"', "\"\"")) } else { String::from(text) };
+    let min_columns: Vec<String> = colors.iter().map(|stats| quote(&format!("min_{}", stats.color))).collect();
+    let mut out = format!("game,{},power,possible,infeasible_draw,exceeded\n", min_columns.join(","));
+    for game in games {
+        let minimums: Vec<String> = colors.iter().map(|stats| game.minimum.get(&stats.color).to_string()).collect();
+        let (possible, draw, exceeded) = match &game.infeasible {
+            None => ("true", String::new(), String::new()),
+            Some((draw, exceeded)) => ("false", draw.to_string(), exceeded.join("; "))
+        };
+        out.push_str(&format!("{},{},{},{possible},{draw},{}\n", game.id, minimums.join(","), game.power, quote(&exceeded)));
+    }
+
+    out.push_str("\ncolour,draws,total,min,max,mean,median\n");
+    for stats in colors {
+        out.push_str(&format!(
+            "{},{},{},{},{},{:.4},{}\n",
+            quote(&stats.color), stats.draws, stats.total, stats.min, stats.max, stats.mean, stats.median
+        ));
+    }
+    out.pop();
+    out
+}
+
+/// IDs of the games that could have been played with `bag`
+fn possible_games(records: &[GameRecord], bag: &Cubes) -> Vec<u32> {
+    records.iter().filter(|r| r.possible(bag)).map(|r| r.id).collect()
+}
+
+/// The fewest cubes of each colour that make every one of `records` possible,
+/// listing the colours of `palette` first
+fn smallest_bag(records: &[GameRecord], palette: &Cubes) -> Cubes {
+    let empty = Cubes { counts: palette.counts.iter().map(|(color, _)| (color.clone(), 0)).collect() };
+    records.iter().fold(empty, |bag, r| bag.max(&r.minimum_bag()))
+}
+
+/// A count for each colour, in the order the colours first appeared
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Cubes {
+    counts: Vec<(String, u32)>
+}
+
+impl Cubes {
+    /// A draw such as `3 blue, 4 red`. A colour is whatever follows the count, so `2 dark red` works too.
+    fn parse(record:&str) -> Self {
+        let mut cubes = Self::zeros();
+        let entries = record.split(',').map(|s| s.trim()).filter(|s| !s.is_empty());
+        for entry in entries {
+            let (val_part, color_part) = entry.split_once(' ').expect("Couldn't get a color");
+            let value:u32 = val_part.parse().expect("Couldn't parse value");
+            *cubes.entry(color_part.trim()) += value;
+        }
+        cubes
+    }
+
+    fn zeros() -> Self {
+        Self { counts: Vec::new() }
+    }
+
+    fn entry(&mut self, color: &str) -> &mut u32 {
+        let index = match self.counts.iter().position(|(c, _)| c == color) {
+            Some(index) => index,
+            None => {
+                self.counts.push((String::from(color), 0));
+                self.counts.len() - 1
+            }
+        };
+        &mut self.counts[index].1
+    }
+
+    /// How many of `color`, zero if it never appeared
+    fn get(&self, color: &str) -> u32 {
+        self.counts.iter().find(|(c, _)| c == color).map_or(0, |(_, count)| *count)
+    }
+
+    fn possible(&self, other:&Cubes) -> bool {
+        self.counts.iter().all(|(color, count)| *count <= other.get(color))
+    }
+
+    fn max(&self, other:&Cubes) -> Self {
+        let mut result = self.clone();
+        for (color, count) in &other.counts {
+            let current = result.entry(color);
+            *current = (*current).max(*count);
+        }
+        result
+    }
+
+    /// The product of the counts of the colours in `palette`, or `None` if it doesn't fit in 128 bits
+    fn prod(&self, palette: &Cubes) -> Option<u128> {
+        palette.counts.iter().try_fold(1u128, |product, (color, _)| product.checked_mul(u128::from(self.get(color))))
+    }
+}
+
+impl Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts: Vec<String> = self.counts.iter().map(|(color, count)| format!("{count} {color}")).collect();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+#[derive(Clone)]
+struct GameRecord {
+    id:u32,
+    draws:Vec<Cubes>
+}
+
+impl GameRecord {
+    fn parse(line:String) -> Self {
+        let mut draws:Vec<Cubes> = Vec::new();
+
+        let mut id_split = line.split(": ");
+        let id_part = id_split.next().expect("Couldn't split on colon").strip_prefix("Game ").expect("Line did not start with 'Game '");
+        let record_part = id_split.next().expect("Couldn't split on colon");
+        for draw_record in record_part.split("; ") {
+            draws.push(Cubes::parse(draw_record));
+        }
+        Self {
+            id: id_part.parse().expect("Couldn't parse game ID"),
+            draws
+        }
+    }
+
+    fn possible(&self, maximums:&Cubes) -> bool {
+        self.draws.iter().all(|c| c.possible(maximums))
+    }
+
+    /// The fewest cubes of each colour this game could have been played with
+    fn minimum_bag(&self) -> Cubes {
+        self.draws.iter().fold(Cubes::zeros(), |l, r| l.max(r))
+    }
+
+    /// The product of the minimum bag's counts for the colours in `palette`
+    fn power(&self, palette: &Cubes) -> u128 {
+        self.minimum_bag().prod(palette).unwrap_or_else(|| panic!("The power of game {} doesn't fit in 128 bits", self.id))
+    }
+}
@@ -8,14 +8,14 @@ mod testing{
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day20_test1.txt");
+        let result = part1("./input/2023/day20_test1.txt");
 
         assert_eq!(result, "The product is 32000000")
     }
 
     #[test]
     fn part1_test2() {
-        let result = part1("./input/day20_test2.txt");
+        let result = part1("./input/2023/day20_test2.txt");
 
         assert_eq!(result, "The product is 11687500")
     }
@@ -1,4 +1,5 @@
 use crate::utilities;
+use std::collections::VecDeque;
 use std::ops::Range;
 // use std::collections:HashMap;
 
@@ -8,11 +9,36 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let lines = utilities::string_iterator("./input/day21_test1.txt");
+        let lines = utilities::string_iterator("./input/2023/day21_test1.txt");
         let stepper = Stepper::read(lines);
         let total = stepper.count_destinations(6);
         assert_eq!(total, 16);
     }
+
+    #[test]
+    fn part2_test1() {
+        let lines = utilities::string_iterator("./input/2023/day21_test1.txt");
+        let garden = InfiniteGarden::read(lines);
+        let counts = garden.count_by_steps(500);
+        assert_eq!(counts[6], 16);
+        assert_eq!(counts[10], 50);
+        assert_eq!(counts[50], 1594);
+        assert_eq!(counts[100], 6536);
+        assert_eq!(counts[500], 167004);
+    }
+
+    #[test]
+    fn extrapolation_matches_brute_force() {
+        for seed in 0..4 {
+            let input = crate::generate::generate(21, seed, 11).unwrap();
+            let garden = InfiniteGarden::read(input.lines().map(String::from));
+            let side = garden.squares.numcols;
+            let brute_force = garden.count_by_steps(12 * side);
+            for steps in [3, 2 * side + 1, 9 * side + side / 2, 12 * side] {
+                assert_eq!(garden.count_destinations(steps), brute_force[steps], "seed {seed}, {steps} steps");
+            }
+        }
+    }
 }
 
 pub fn part1(path: &str) -> String {
@@ -22,6 +48,13 @@ pub fn part1(path: &str) -> String {
     format!("He can reach {total} garden plots")
 }
 
+pub fn part2(path: &str) -> String {
+    let lines = utilities::string_iterator(path);
+    let garden = InfiniteGarden::read(lines);
+    let total = garden.count_destinations(26501365);
+    format!("He can reach {total} garden plots")
+}
+
 struct Stepper {
     adjacency: Vec<Vec<usize>>,
     start: usize
@@ -115,6 +148,89 @@ impl Stepper {
     // }
 }
 
+/// The garden map repeated forever in every direction
+struct InfiniteGarden {
+    squares: Chart<Square>,
+    start: (isize, isize)
+}
+
+impl InfiniteGarden {
+    fn read(mut lines: impl Iterator<Item = String>) -> Self {
+        let first_line = lines.next().unwrap();
+        let mut squares = Chart::new(first_line.chars().map(Square::read));
+        for line in lines {
+            squares.append_row(line.chars().map(Square::read));
+        }
+        if squares.numrows != squares.numcols {
+            panic!("Garden should be square");
+        }
+        let start = squares.find_with(| square | square.is_start());
+        let start = ((start / squares.numcols) as isize, (start % squares.numcols) as isize);
+        Self { squares, start }
+    }
+
+    /// Number of plots reachable in exactly `steps` steps, for every step count up to `max_steps`.
+    /// A plot first reached in `d` steps can be reached in any `d + 2k` steps by stepping back and forth,
+    /// so one BFS gives every count.
+    fn count_by_steps(&self, max_steps: usize) -> Vec<usize> {
+        let side = 2 * max_steps + 1;
+        let offset = max_steps as isize;
+        let to_slot = | (row, col): (isize, isize) | ((row - self.start.0 + offset) * side as isize + col - self.start.1 + offset) as usize;
+
+        // the BFS gives the distances in order, so all that needs keeping is whether a square was reached
+        let mut seen = vec![false; side * side];
+        let mut first_reached: Vec<usize> = vec![0; max_steps + 1];
+        let mut queue = VecDeque::from([(self.start, 0)]);
+        seen[to_slot(self.start)] = true;
+        while let Some((position, steps_taken)) = queue.pop_front() {
+            first_reached[steps_taken] += 1;
+            if steps_taken == max_steps {
+                continue;
+            }
+            for (neighbor, index) in self.squares.tiled_neigbors(position) {
+                let slot = to_slot(neighbor);
+                if self.squares.entries[index].is_plot() && !seen[slot] {
+                    seen[slot] = true;
+                    queue.push_back((neighbor, steps_taken + 1));
+                }
+            }
+        }
+
+        let mut counts = first_reached.clone();
+        for steps in 2..=max_steps {
+            counts[steps] += counts[steps - 2];
+        }
+        counts
+    }
+
+    /// Past the first few tiles, the count after `remainder + k * side` steps grows quadratically in `k`.
+    /// Brute force until the second differences settle, then extrapolate.
+    fn count_destinations(&self, steps: usize) -> usize {
+        let side = self.squares.numcols;
+        let remainder = steps % side;
+        let target = steps / side;
+        let max_tiles = 12;
+        let counts = self.count_by_steps(remainder + side * target.min(max_tiles));
+        let samples: Vec<usize> = (0..=target.min(max_tiles)).map(| k | counts[remainder + k * side]).collect();
+        if target <= max_tiles {
+            return samples[target];
+        }
+
+        // signed, since the counts can dip before they settle
+        let samples: Vec<i64> = samples.into_iter().map(| count | count as i64).collect();
+        let second_difference = | k: usize | samples[k] + samples[k - 2] - 2 * samples[k - 1];
+        let settled = (4..=max_tiles)
+            .find(| k | second_difference(*k) == second_difference(k - 1) && second_difference(k - 1) == second_difference(k - 2))
+            .expect("Reachable counts never settled into a quadratic");
+
+        let (a, b, c) = (samples[settled - 2], samples[settled - 1], samples[settled]);
+        let x = (target - (settled - 2)) as i64;
+        let (first, second) = (b - a, c + a - 2 * b);
+        let count = a + x * first + x * (x - 1) / 2 * second;
+        usize::try_from(count).expect("Extrapolated a negative number of plots")
+    }
+}
+
 struct Chart<T> {
     entries: Vec<T>,
     numrows: usize,
@@ -165,6 +281,18 @@ impl <T> Chart<T> {
             .collect()
     }
 
+    /// Neighbors when the chart repeats forever in every direction,
+    /// paired with the index of the entry each one is a copy of
+    fn tiled_neigbors(&self, (row, col): (isize, isize)) -> [((isize, isize), usize); 4] {
+        [(row - 1, col), (row + 1, col), (row, col - 1), (row, col + 1)].map(
+            | (r, c) | {
+                let index = r.rem_euclid(self.numrows as isize) as usize * self.numcols
+                    + c.rem_euclid(self.numcols as isize) as usize;
+                ((r, c), index)
+            }
+        )
+    }
+
     fn find_with(&self, test: impl Fn(&T) -> bool) -> usize {
         self.entries
             .iter()
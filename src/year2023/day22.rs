@@ -0,0 +1,126 @@
+use crate::utilities;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn part1_test1() {
+        let result = part1("./input/2023/day22_test1.txt");
+
+        assert_eq!(result, "5 bricks could be safely disintegrated");
+    }
+
+    #[test]
+    fn part2_test1() {
+        let result = part2("./input/2023/day22_test1.txt");
+
+        assert_eq!(result, "The sum of other bricks that would fall is 7");
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let lines = utilities::string_iterator(path);
+    let pile = Pile::settle(lines.map(|l| Brick::read(&l)).collect());
+    let safe = pile.range().filter(| brick | pile.is_safe(*brick)).count();
+
+    format!("{safe} bricks could be safely disintegrated")
+}
+
+pub fn part2(path: &str) -> String {
+    let lines = utilities::string_iterator(path);
+    let pile = Pile::settle(lines.map(|l| Brick::read(&l)).collect());
+    let total: usize = pile.range().map(| brick | pile.falls_without(brick)).sum();
+
+    format!("The sum of other bricks that would fall is {total}")
+}
+
+struct Brick {
+    low: [usize; 3],
+    high: [usize; 3]
+}
+
+impl Brick {
+    fn read(line: &str) -> Self {
+        let (first, second) = line.split_once('~').expect("Brick should have two ends");
+        let corner = | s: &str | {
+            let coordinates: Vec<usize> = s.split(',').map(| n | n.parse().expect("Bad coordinate")).collect();
+            [coordinates[0], coordinates[1], coordinates[2]]
+        };
+        let (a, b) = (corner(first), corner(second));
+        let low = [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])];
+        let high = [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])];
+        Self { low, high }
+    }
+
+    /// The (x, y) columns the brick occupies
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.low[0]..=self.high[0]).flat_map(move | x | (self.low[1]..=self.high[1]).map(move | y | (x, y)))
+    }
+}
+
+/// Bricks after they have all fallen, ordered from the ground up
+struct Pile {
+    supported_by: Vec<Vec<usize>>,
+    supports: Vec<Vec<usize>>
+}
+
+impl Pile {
+    /// Drops bricks lowest first, tracking the top brick of every column in a height map
+    fn settle(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort_by_key(| brick | brick.low[2]);
+        let mut height_map: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut supported_by: Vec<Vec<usize>> = vec![Vec::new(); bricks.len()];
+        let mut supports: Vec<Vec<usize>> = vec![Vec::new(); bricks.len()];
+
+        for (index, brick) in bricks.iter().enumerate() {
+            let resting_height = brick.footprint()
+                .filter_map(| column | height_map.get(&column))
+                .map(| (height, _) | *height)
+                .max()
+                .unwrap_or(0);
+
+            for column in brick.footprint() {
+                if let Some((height, below)) = height_map.get(&column) {
+                    if *height == resting_height && !supported_by[index].contains(below) {
+                        supported_by[index].push(*below);
+                        supports[*below].push(index);
+                    }
+                }
+            }
+
+            let top = resting_height + 1 + brick.high[2] - brick.low[2];
+            for column in brick.footprint() {
+                height_map.insert(column, (top, index));
+            }
+        }
+
+        Self { supported_by, supports }
+    }
+
+    fn range(&self) -> std::ops::Range<usize> {
+        0..self.supports.len()
+    }
+
+    /// Safe when everything it holds up has another support
+    fn is_safe(&self, brick: usize) -> bool {
+        self.supports[brick].iter().all(| above | self.supported_by[*above].len() > 1)
+    }
+
+    /// How many other bricks would fall if `removed` were disintegrated.
+    /// Bricks are in settling order, so every supporter is decided before the bricks it holds up.
+    fn falls_without(&self, removed: usize) -> usize {
+        let mut falling = vec![false; self.supports.len()];
+        falling[removed] = true;
+        let mut count = 0;
+        for brick in removed + 1..self.supports.len() {
+            let below = &self.supported_by[brick];
+            if !below.is_empty() && below.iter().all(| support | falling[*support]) {
+                falling[brick] = true;
+                count += 1;
+            }
+        }
+        count
+    }
+}
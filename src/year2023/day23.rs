@@ -0,0 +1,239 @@
+use crate::utilities;
+use std::ops::Range;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn part1_test1() {
+        let result = part1("./input/2023/day23_test1.txt");
+
+        assert_eq!(result, "The longest hike is 94 steps");
+    }
+
+    #[test]
+    fn part2_test1() {
+        let result = part2("./input/2023/day23_test1.txt");
+
+        assert_eq!(result, "The longest hike is 154 steps");
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let lines = utilities::string_iterator(path);
+    let trails = Trails::read(lines);
+    let longest = trails.longest_hike(true);
+    format!("The longest hike is {longest} steps")
+}
+
+pub fn part2(path: &str) -> String {
+    let lines = utilities::string_iterator(path);
+    let trails = Trails::read(lines);
+    let longest = trails.longest_hike(false);
+    format!("The longest hike is {longest} steps")
+}
+
+/// The trail map compressed to its junctions, with the corridors between them as weighted edges
+struct Trails {
+    /// (other junction, length, whether it can be walked without climbing a slope)
+    edges: Vec<Vec<(usize, usize, bool)>>,
+    start: usize,
+    end: usize
+}
+
+impl Trails {
+    fn read(mut lines: impl Iterator<Item = String>) -> Self {
+        let first_line = lines.next().unwrap();
+        let mut map = Chart::new(first_line.chars().map(Tile::read));
+        for line in lines {
+            map.append_row(line.chars().map(Tile::read));
+        }
+
+        let start_square = map.find_with(| tile | tile.is_open());
+        let end_square = map.range().rev().find(| i | map.entries[*i].is_open()).unwrap();
+
+        // junctions are the ends of the trail plus every square where paths meet
+        let junctions: Vec<usize> = map
+            .range()
+            .filter(
+                | i | {
+                    map.entries[*i].is_open() && (
+                        *i == start_square || *i == end_square ||
+                        map.open_neighbors(*i).len() >= 3
+                    )
+                }
+            )
+            .collect();
+        if junctions.len() > 64 {
+            panic!("Too many junctions for a 64 bit visited set");
+        }
+
+        let edges = junctions
+            .iter()
+            .map(
+                | junction | {
+                    map.open_neighbors(*junction)
+                        .into_iter()
+                        .filter_map(| first_step | map.follow_corridor(*junction, first_step, &junctions))
+                        .collect()
+                }
+            )
+            .collect();
+
+        let start = junctions.iter().position(| j | *j == start_square).unwrap();
+        let end = junctions.iter().position(| j | *j == end_square).unwrap();
+        Self { edges, start, end }
+    }
+
+    /// Depth first search over every simple path from start to end
+    fn longest_hike(&self, slippery: bool) -> usize {
+        // the last junction before the end has to go straight there, or the end is cut off
+        let (last_junction, last_length) = self.edges[self.end]
+            .iter()
+            .map(| (junction, length, _) | (*junction, *length))
+            .next()
+            .expect("End of the trail is unreachable");
+
+        let mut longest: Option<usize> = None;
+        let mut stack: Vec<(usize, u64, usize)> = vec![(self.start, 1 << self.start, 0)];
+        while let Some((junction, visited, length)) = stack.pop() {
+            if junction == last_junction {
+                longest = longest.max(Some(length + last_length));
+                continue;
+            }
+            for (next, edge_length, downhill) in &self.edges[junction] {
+                if (*downhill || !slippery) && visited & (1 << next) == 0 {
+                    stack.push((*next, visited | (1 << next), length + edge_length));
+                }
+            }
+        }
+
+        longest.expect("No hike reaches the end")
+    }
+}
+
+struct Chart<T> {
+    entries: Vec<T>,
+    numrows: usize,
+    numcols: usize
+}
+
+impl <T> Chart<T> {
+    fn new(first_row: impl Iterator<Item = T>) -> Self {
+        let entries: Vec<T> = first_row.collect();
+        let numrows = 1;
+        let numcols = entries.len();
+        Self { entries, numrows, numcols }
+    }
+
+    fn append_row(&mut self, new_row: impl Iterator<Item = T>) {
+        self.entries.extend(new_row);
+        self.numrows += 1;
+        if self.entries.len() != self.numcols * self.numrows {
+            panic!("New row was wrong length");
+        }
+    }
+
+    fn range(&self) -> Range<usize> {
+        0..self.entries.len()
+    }
+
+    /// Squares up, down, left and right, paired with the direction of the step
+    fn neighbors(&self, index: usize) -> Vec<(usize, Direction)> {
+        let col = index % self.numcols;
+        let row = index / self.numcols;
+        let mut neighbors = Vec::new();
+        if row > 0 {
+            neighbors.push((index - self.numcols, Direction::N));
+        }
+        if row + 1 < self.numrows {
+            neighbors.push((index + self.numcols, Direction::S));
+        }
+        if col > 0 {
+            neighbors.push((index - 1, Direction::W));
+        }
+        if col + 1 < self.numcols {
+            neighbors.push((index + 1, Direction::E));
+        }
+        neighbors
+    }
+
+    fn find_with(&self, test: impl Fn(&T) -> bool) -> usize {
+        self.entries
+            .iter()
+            .position(test)
+            .unwrap()
+    }
+}
+
+impl Chart<Tile> {
+    fn open_neighbors(&self, index: usize) -> Vec<(usize, Direction)> {
+        self.neighbors(index)
+            .into_iter()
+            .filter(| (i, _) | self.entries[*i].is_open())
+            .collect()
+    }
+
+    /// Walks a corridor from `junction` until the next junction, returning that junction's position
+    /// in `junctions`, the length walked and whether every step went with the slopes.
+    /// `None` for dead ends.
+    fn follow_corridor(&self, junction: usize, first_step: (usize, Direction), junctions: &[usize]) -> Option<(usize, usize, bool)> {
+        let (mut current, direction) = first_step;
+        let mut previous = junction;
+        let mut downhill = self.entries[junction].allows(direction);
+        let mut length = 1;
+        loop {
+            if let Some(position) = junctions.iter().position(| j | *j == current) {
+                return Some((position, length, downhill));
+            }
+            let (next, next_direction) = self.open_neighbors(current)
+                .into_iter()
+                .find(| (i, _) | *i != previous)?;
+            downhill &= self.entries[current].allows(next_direction);
+            previous = current;
+            current = next;
+            length += 1;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    N,
+    S,
+    E,
+    W
+}
+
+enum Tile {
+    Path,
+    Forest,
+    Slope(Direction)
+}
+
+impl Tile {
+    fn read(ch: char) -> Self {
+        match ch {
+            '.' => Self::Path,
+            '#' => Self::Forest,
+            '^' => Self::Slope(Direction::N),
+            'v' => Self::Slope(Direction::S),
+            '>' => Self::Slope(Direction::E),
+            '<' => Self::Slope(Direction::W),
+            _ => panic!("Invalid character")
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        !matches!(self, Self::Forest)
+    }
+
+    /// Whether you can step off this tile in `direction`
+    fn allows(&self, direction: Direction) -> bool {
+        match self {
+            Self::Slope(slope) => *slope == direction,
+            _ => true
+        }
+    }
+}
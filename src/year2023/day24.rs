@@ -0,0 +1,189 @@
+use crate::rational::Rational;
+use crate::utilities;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn part1_test1() {
+        let stones: Vec<Hailstone> = utilities::string_iterator("./input/2023/day24_test1.txt")
+            .map(|l| Hailstone::read(&l))
+            .collect();
+
+        assert_eq!(count_crossings(&stones, 7, 27), 2);
+    }
+
+    #[test]
+    fn part2_test1() {
+        let result = part2("./input/2023/day24_test1.txt");
+
+        assert_eq!(result, "The rock's starting coordinates add up to 47");
+    }
+
+    #[test]
+    fn full_size_rock() {
+        let mut rng = crate::rng::Rng::new(24);
+        let mut coordinate = | low: usize, high: usize | rng.range(low..high) as i128;
+        let position = [coordinate(200000000000000, 400000000000000), coordinate(200000000000000, 400000000000000), coordinate(200000000000000, 400000000000000)];
+        let velocity = [coordinate(0, 600) - 300, coordinate(0, 600) - 300, coordinate(0, 600) - 300];
+        let stones: Vec<Hailstone> = (0..5)
+            .map(
+                | _ | {
+                    let time = coordinate(100000000000, 1000000000000);
+                    let stone_velocity: [i128; 3] = std::array::from_fn(| _ | coordinate(0, 600) - 300);
+                    Hailstone {
+                        position: std::array::from_fn(| k | position[k] + time * (velocity[k] - stone_velocity[k])),
+                        velocity: stone_velocity
+                    }
+                }
+            )
+            .collect();
+
+        let rock = throw_rock(&stones);
+        assert_eq!(rock.position, position);
+        assert_eq!(rock.velocity, velocity);
+    }
+
+    #[test]
+    fn elimination_detects_singular() {
+        let row = | values: [i128; 3] | values.map(Rational::from).to_vec();
+        let solution = solve_linear(vec![row([2, 1, 5]), row([1, -1, 1])]).unwrap();
+        assert_eq!(solution, vec![Rational::from(2), Rational::from(1)]);
+        assert!(solve_linear(vec![row([2, 4, 6]), row([1, 2, 3])]).is_none());
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let stones: Vec<Hailstone> = utilities::string_iterator(path)
+        .map(|l| Hailstone::read(&l))
+        .collect();
+    let crossings = count_crossings(&stones, 200000000000000, 400000000000000);
+
+    format!("{crossings} pairs of paths cross inside the test area")
+}
+
+pub fn part2(path: &str) -> String {
+    let stones: Vec<Hailstone> = utilities::string_iterator(path)
+        .map(|l| Hailstone::read(&l))
+        .collect();
+    let rock = throw_rock(&stones);
+    let total: i128 = rock.position.iter().sum();
+
+    format!("The rock's starting coordinates add up to {total}")
+}
+
+struct Hailstone {
+    position: [i128; 3],
+    velocity: [i128; 3]
+}
+
+impl Hailstone {
+    fn read(line: &str) -> Self {
+        let (position, velocity) = line.split_once('@').expect("Hailstone should have a velocity");
+        let triple = | s: &str | {
+            let values: Vec<i128> = s.split(',').map(| n | n.trim().parse().expect("Bad number")).collect();
+            [values[0], values[1], values[2]]
+        };
+        Self { position: triple(position), velocity: triple(velocity) }
+    }
+
+    /// Whether the x-y paths cross at or after time zero for both stones, inside the square `low..=high`.
+    /// Solves `p + t v = q + s w` with Cramer's rule, keeping everything as integer numerators over `det`.
+    fn crosses_within(&self, other: &Self, low: i128, high: i128) -> bool {
+        let [px, py, _] = self.position;
+        let [vx, vy, _] = self.velocity;
+        let [qx, qy, _] = other.position;
+        let [wx, wy, _] = other.velocity;
+
+        let det = wx * vy - vx * wy;
+        if det == 0 {
+            // parallel paths never cross at a single point
+            return false;
+        }
+        let (dx, dy) = (qx - px, qy - py);
+        let t = wx * dy - wy * dx;
+        let s = vx * dy - vy * dx;
+
+        // compare everything multiplied by det, flipping if det is negative
+        let sign = det.signum();
+        let (det, t, s) = (det * sign, t * sign, s * sign);
+        if t < 0 || s < 0 {
+            return false;
+        }
+        let x = px * det + t * vx;
+        let y = py * det + t * vy;
+        (low * det..=high * det).contains(&x) && (low * det..=high * det).contains(&y)
+    }
+}
+
+fn count_crossings(stones: &[Hailstone], low: i128, high: i128) -> usize {
+    stones
+        .iter()
+        .enumerate()
+        .map(| (i, stone) | stones[i + 1..].iter().filter(| other | stone.crosses_within(other, low, high)).count())
+        .sum()
+}
+
+/// Finds the line that hits every hailstone.
+/// Relative to the first stone the rock passes through the origin, so its position `P` and velocity `V`
+/// are parallel, and for each other stone `(P - p) x (V - v) = 0` becomes linear: `P x v + p x V = p x v`.
+fn throw_rock(stones: &[Hailstone]) -> Hailstone {
+    if stones.len() < 4 {
+        panic!("Need at least four hailstones to pin down the rock");
+    }
+    let origin = &stones[0];
+    let mut rows: Vec<Vec<Rational>> = Vec::new();
+    for stone in &stones[1..4] {
+        let p: [i128; 3] = std::array::from_fn(| k | stone.position[k] - origin.position[k]);
+        let v: [i128; 3] = std::array::from_fn(| k | stone.velocity[k] - origin.velocity[k]);
+        let rhs = cross(p, v);
+        // unknowns are P0, P1, P2, V0, V1, V2
+        let coefficients = [
+            [0, v[2], -v[1], 0, -p[2], p[1], rhs[0]],
+            [-v[2], 0, v[0], p[2], 0, -p[0], rhs[1]],
+            [v[1], -v[0], 0, -p[1], p[0], 0, rhs[2]]
+        ];
+        rows.extend(coefficients.into_iter().map(| row | row.map(Rational::from).to_vec()));
+    }
+
+    let solution: Vec<i128> = solve_linear(rows)
+        .expect("Hailstones don't determine a single rock")
+        .iter()
+        .map(| value | value.to_integer().expect("Rock should start at whole coordinates"))
+        .collect();
+
+    Hailstone {
+        position: std::array::from_fn(| k | solution[k] + origin.position[k]),
+        velocity: std::array::from_fn(| k | solution[k + 3] + origin.velocity[k])
+    }
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Gaussian elimination on an augmented matrix with at least as many rows as unknowns.
+/// `None` unless there is exactly one solution.
+fn solve_linear(mut rows: Vec<Vec<Rational>>) -> Option<Vec<Rational>> {
+    let unknowns = rows.first()?.len() - 1;
+    for col in 0..unknowns {
+        let pivot = (col..rows.len()).find(| r | !rows[*r][col].is_zero())?;
+        rows.swap(col, pivot);
+        let pivot_row = rows[col].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == col || row[col].is_zero() {
+                continue;
+            }
+            let factor = &row[col] / &pivot_row[col];
+            for (value, pivot_value) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *value = &*value - &(&factor * pivot_value);
+            }
+        }
+    }
+    // leftover rows must have been fully cancelled, or the system is inconsistent
+    if rows[unknowns..].iter().any(| row | !row[unknowns].is_zero()) {
+        return None;
+    }
+    Some((0..unknowns).map(| i | &rows[i][unknowns] / &rows[i][i]).collect())
+}
@@ -0,0 +1,146 @@
+use crate::utilities;
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn part1_test1() {
+        let result = part1("./input/2023/day25_test1.txt");
+
+        assert_eq!(result, "The product of the group sizes is 54 (cut bvb/cmg, hfx/pzl, jqt/nvd)");
+    }
+
+    #[test]
+    fn no_cut_of_that_size() {
+        let lines = utilities::string_iterator("./input/2023/day25_test1.txt");
+        let wiring = Wiring::read(lines);
+
+        assert!(wiring.find_cut(2).is_none());
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let lines = utilities::string_iterator(path);
+    let wiring = Wiring::read(lines);
+    let cut = wiring.find_cut(3).expect("No three wires split the machine");
+    let (left, right) = cut.group_sizes;
+    let wires: Vec<String> = cut.wires.iter().map(| (a, b) | format!("{a}/{b}")).collect();
+
+    format!("The product of the group sizes is {} (cut {})", left * right, wires.join(", "))
+}
+
+/// Undirected graph of components, each wire an edge
+struct Wiring {
+    names: Vec<String>,
+    adjacency: Vec<Vec<usize>>
+}
+
+/// The two groups left after cutting `wires`, each wire named in alphabetical order
+struct Cut {
+    group_sizes: (usize, usize),
+    wires: Vec<(String, String)>
+}
+
+impl Wiring {
+    fn read(lines: impl Iterator<Item = String>) -> Self {
+        let mut names: Vec<String> = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        let mut adjacency: Vec<Vec<usize>> = Vec::new();
+        let mut index_of = | name: &str, adjacency: &mut Vec<Vec<usize>> | {
+            *indices.entry(String::from(name)).or_insert_with(
+                || {
+                    names.push(String::from(name));
+                    adjacency.push(Vec::new());
+                    adjacency.len() - 1
+                }
+            )
+        };
+
+        for line in lines {
+            let (component, others) = line.split_once(':').expect("Line should have a colon");
+            let a = index_of(component.trim(), &mut adjacency);
+            for other in others.split_whitespace() {
+                let b = index_of(other, &mut adjacency);
+                adjacency[a].push(b);
+                adjacency[b].push(a);
+            }
+        }
+
+        Self { names, adjacency }
+    }
+
+    /// Deterministic minimum cut search: every wire has capacity 1, so the first component whose
+    /// max flow from component 0 is exactly `size` lies across a cut of that many wires.
+    /// Edmonds-Karp stops early once the flow exceeds `size`.
+    fn find_cut(&self, size: usize) -> Option<Cut> {
+        let source = 0;
+        for sink in 1..self.names.len() {
+            let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+            let mut total = 0;
+            while total <= size {
+                match self.augmenting_path(source, sink, &flow) {
+                    Ok(path) => {
+                        for pair in path.windows(2) {
+                            *flow.entry((pair[0], pair[1])).or_insert(0) += 1;
+                            *flow.entry((pair[1], pair[0])).or_insert(0) -= 1;
+                        }
+                        total += 1;
+                    },
+                    Err(reachable) => {
+                        if total == size {
+                            return Some(self.cut_from(&reachable));
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Shortest path with spare capacity from `source` to `sink`,
+    /// or everything reachable from `source` if there isn't one
+    fn augmenting_path(&self, source: usize, sink: usize, flow: &HashMap<(usize, usize), i32>) -> Result<Vec<usize>, Vec<bool>> {
+        let mut came_from: Vec<Option<usize>> = vec![None; self.names.len()];
+        let mut reached = vec![false; self.names.len()];
+        reached[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(current) = queue.pop_front() {
+            for next in &self.adjacency[current] {
+                let used = flow.get(&(current, *next)).copied().unwrap_or(0);
+                if !reached[*next] && used < 1 {
+                    reached[*next] = true;
+                    came_from[*next] = Some(current);
+                    queue.push_back(*next);
+                }
+            }
+            if reached[sink] {
+                let mut path = vec![sink];
+                while let Some(previous) = came_from[*path.last().unwrap()] {
+                    path.push(previous);
+                }
+                path.reverse();
+                return Ok(path);
+            }
+        }
+        Err(reached)
+    }
+
+    fn cut_from(&self, side: &[bool]) -> Cut {
+        let inside = side.iter().filter(| in_side | **in_side).count();
+        let mut wires: Vec<(String, String)> = Vec::new();
+        for (a, neighbors) in self.adjacency.iter().enumerate() {
+            for b in neighbors {
+                if side[a] && !side[*b] {
+                    let (first, second) = (&self.names[a], &self.names[*b]);
+                    let wire = if first < second { (first, second) } else { (second, first) };
+                    wires.push((wire.0.clone(), wire.1.clone()));
+                }
+            }
+        }
+        wires.sort();
+        Cut { group_sizes: (inside, self.names.len() - inside), wires }
+    }
+}
@@ -0,0 +1,602 @@
+use crate::{options, utilities};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    
+    #[test]
+    fn test_data_1() {
+        let output = part1("./input/2023/day3_test1.txt");
+        assert_eq!(output, "The sum of parts values is 4361");
+    }
+
+    #[test]
+    fn test_data_2() {
+        let output = part2("./input/2023/day3_test2.txt");
+        assert_eq!(output, "The sum of gear values is 467835");
+    }
+
+    fn example() -> Schematic {
+        let lines = utilities::lines_from_file("./input/2023/day3_test1.txt").filter_map(|s| s.ok());
+        let mut schematic = Grid::new();
+        schematic.read_lines(lines).unwrap();
+        schematic.schematic()
+    }
+
+    #[test]
+    fn numbers_and_symbols_link_both_ways() {
+        let schematic = example();
+        let star = schematic.symbols.iter().position(|(_, ch)| *ch == '*').unwrap();
+        let near_star: Vec<u32> = schematic.numbers_near(star).map(|(_, num)| num).collect();
+        assert_eq!(near_star, vec![467, 35]);
+
+        let number = schematic.numbers.iter().position(|(_, num)| *num == 633).unwrap();
+        let near_number: Vec<(Position, char)> = schematic.symbols_near(number).collect();
+        assert_eq!(near_number, vec![(Position { line: 3, cols: (6, 6) }, '#')]);
+        assert_eq!(schematic.part_numbers().len(), 8);
+    }
+
+    #[test]
+    fn exports_uneven_rows() {
+        let lines: Vec<String> = ["12.", "*", "..3"].iter().map(|line| String::from(*line)).collect();
+        let mut grid = Grid::new();
+        let row_lengths = grid.read_lines_padded(&lines);
+        let schematic = grid.schematic();
+        assert_eq!(row_lengths, vec![3, 1, 3]);
+
+        let dot = schematic_dot(&schematic);
+        assert!(dot.contains("n0 [label=\"12\", pos=\"0.5,0!\"];"));
+        assert!(dot.contains("n1 [label=\"3\", pos=\"2,-2!\", color=red];"));
+        assert!(dot.contains("n0 -- s0;"));
+
+        let json = schematic_json(&schematic, &row_lengths);
+        assert!(json.contains("\"uneven_rows\": [{\"line\": 1, \"length\": 1}]"));
+        assert!(json.contains("{\"id\": \"s0\", \"symbol\": \"*\", \"line\": 1, \"col\": 0, \"numbers\": [\"n0\"]}"));
+        assert!(json.contains("{\"id\": \"n1\", \"value\": 3, \"line\": 2, \"cols\": [2, 2], \"symbols\": []}"));
+    }
+
+    #[test]
+    fn export_command_lists_uneven_rows() {
+        let dir = std::env::temp_dir().join("aoc2023_day3_export");
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("uneven.txt");
+        fs::write(&input, "12.\n*\n..3\n").unwrap();
+
+        let message = export(input.to_str().unwrap(), &dir.join("graph.json")).unwrap();
+        assert_eq!(message, format!(
+            "Wrote 2 numbers and 1 symbols to {}\nRows should be 3 characters long, but line 2 has 1",
+            dir.join("graph.json").display()
+        ));
+        assert!(fs::read_to_string(dir.join("graph.json")).unwrap().contains("\"uneven_rows\": [{\"line\": 1, \"length\": 1}]"));
+        assert!(export(input.to_str().unwrap(), &dir.join("graph.png")).is_err());
+    }
+
+    #[test]
+    fn streaming_matches_the_whole_grid() {
+        for (seed, size) in [(1, 12), (2, 40), (3, 1), (4, 2)] {
+            let input = crate::generate::generate(3, seed, size).unwrap();
+            let mut grid = Grid::new();
+            grid.read_lines(input.lines().map(String::from)).unwrap();
+            let schematic = grid.schematic();
+            let gears = SymbolRule::parse("*:2:product").unwrap();
+
+            let mut parts = Vec::new();
+            let mut gear_values = Vec::new();
+            stream_schematic(input.lines().map(String::from), |finding| match finding {
+                Finding::Part(num) => parts.push(num),
+                Finding::Symbol { ch, numbers, .. } => gear_values.extend(gears.value(ch, &numbers))
+            }).unwrap();
+            assert_eq!(parts, schematic.part_numbers());
+            assert_eq!(gear_values, schematic.apply(&gears));
+        }
+    }
+
+    #[test]
+    fn uneven_rows_are_errors() {
+        let lines = ["467..", "...*.", "35.", "....."].iter().map(|line| String::from(*line));
+        let mut findings = Vec::new();
+        let err = stream_schematic(lines, |finding| findings.push(finding)).unwrap_err();
+        assert_eq!(err, SchematicError::UnevenRow { line: 3, expected: 5, found: 3 });
+        assert_eq!(findings, vec![Finding::Part(467)]);
+
+        let lines = ["467..", "...*.", "35.", "....."].iter().map(|line| String::from(*line));
+        assert_eq!(Grid::new().read_lines(lines), Err(err));
+    }
+
+    #[test]
+    fn configurable_rules() {
+        let schematic = example();
+        let total = |rules: &str| -> u64 {
+            SymbolRule::parse_list(rules).unwrap().iter().flat_map(|rule| schematic.apply(rule)).sum()
+        };
+        assert_eq!(total("*:2:product"), 467835);
+        assert_eq!(total("*:1:sum"), 617);
+        assert_eq!(total("any:1:sum"), 633 + 617 + 592 + 664);
+        assert_eq!(total("*:2+:sum #:1:product"), 467 + 35 + 755 + 598 + 633);
+        assert!(SymbolRule::parse_list("*:two:sum").is_err());
+        assert!(SymbolRule::parse_list("*:2:mean").is_err());
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    if options::get_or("stream", false) {
+        let mut parts_sum = 0u64;
+        stream_lines(path, |finding| if let Finding::Part(num) = finding { parts_sum += u64::from(num) })
+            .unwrap_or_else(|err| panic!("{err}"));
+        return format!("The sum of parts values is {parts_sum}");
+    }
+    let parts_nums = read_schematic(path).unwrap_or_else(|err| panic!("{err}")).part_numbers();
+    let parts_sum:u32 = parts_nums.into_iter().sum();
+    format!("The sum of parts values is {parts_sum}")
+}
+
+pub fn part2(path: &str) -> String {
+    let rules = options::get("rules").unwrap_or_else(|| String::from("*:2:product"));
+    let rules = SymbolRule::parse_list(&rules).unwrap_or_else(|err| panic!("{err}"));
+    if options::get_or("stream", false) {
+        let mut gears_sum = 0u64;
+        stream_lines(path, |finding| if let Finding::Symbol { ch, numbers, .. } = finding {
+            gears_sum += rules.iter().filter_map(|rule| rule.value(ch, &numbers)).sum::<u64>();
+        }).unwrap_or_else(|err| panic!("{err}"));
+        return format!("The sum of gear values is {gears_sum}");
+    }
+    let schematic = read_schematic(path).unwrap_or_else(|err| panic!("{err}"));
+    let gears_sum:u64 = rules.iter().flat_map(|rule| schematic.apply(rule)).sum();
+    format!("The sum of gear values is {gears_sum}")
+}
+
+fn read_schematic(path: &str) -> Result<Schematic, SchematicError> {
+    let lines = 
+        utilities::lines_from_file(path)
+            .filter_map(|s| s.ok());
+    let mut grid = Grid::new();
+    grid.read_lines(lines)?;
+    Ok(grid.schematic())
+}
+
+/// `export 3 <input> <graph.dot|graph.json>` writes the number-symbol graph of a schematic, with positions.
+/// Short rows are padded so the graph can still be drawn; they are listed in the JSON and in the returned message.
+pub fn export(input: &str, output: &Path) -> Result<String, String> {
+    if !matches!(output.extension().and_then(|ext| ext.to_str()), Some("dot" | "json")) {
+        return Err(format!("Can't export to {}: expected a .dot or .json file", output.display()));
+    }
+    let lines: Vec<String> = fs::read_to_string(input)
+        .map_err(|err| format!("Couldn't read {input}: {err}"))?
+        .lines()
+        .map(String::from)
+        .collect();
+    let mut grid = Grid::new();
+    let row_lengths = grid.read_lines_padded(&lines);
+    let schematic = grid.schematic();
+    write_export(&schematic, &row_lengths, output).map_err(|err| format!("Couldn't write {}: {err}", output.display()))?;
+
+    let mut message = format!(
+        "Wrote {} numbers and {} symbols to {}",
+        schematic.numbers.len(), schematic.symbols.len(), output.display()
+    );
+    let expected = row_lengths.first().copied().unwrap_or(0);
+    let uneven: Vec<String> = row_lengths.iter().enumerate()
+        .filter(|(_, len)| **len != expected)
+        .map(|(line, len)| format!("line {} has {len}", line + 1))
+        .collect();
+    if !uneven.is_empty() {
+        message.push_str(&format!("\nRows should be {expected} characters long, but {}", uneven.join(", ")));
+    }
+    Ok(message)
+}
+
+/// Streams the file through `stream_schematic`
+fn stream_lines(path: &str, found: impl FnMut(Finding)) -> Result<(), SchematicError> {
+    let lines = utilities::lines_from_file(path).map_while(Result::ok);
+    stream_schematic(lines, found)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Position {
+    line: usize,
+    cols: (usize, usize)
+}
+
+impl Position {
+    fn iter_adjacent(&self) -> impl Iterator<Item = (usize, usize)> {
+        let mut adjacent_idx:Vec<(i32, i32)> = Vec::new();
+        let row = self.line as i32;
+        let start_col = self.cols.0 as i32;
+        let end_col = self.cols.1 as i32;
+
+        for col in start_col-1 ..= end_col+1 {
+            adjacent_idx.push((row-1, col));
+        }
+        adjacent_idx.push((row, start_col-1));
+        adjacent_idx.push((row, end_col+1));
+        for col in start_col-1 ..= end_col+1 {
+            adjacent_idx.push((row+1, col));
+        }
+
+        adjacent_idx.into_iter().filter(|(row, col)| {*row >= 0 && *col >= 0}).map(|(row, col)| {(row as usize, col as usize)})
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Element {
+    Empty(),
+    Symbol { pos:Position, ch:char },
+    Number { pos:Position, num:u32 }
+}
+
+struct Grid {
+    num_rows: usize,
+    num_cols: usize,
+    elements: Vec<Element>,
+    grid: Vec<usize>
+}
+
+impl Grid {
+    fn new() -> Self {
+        let elements = vec![Element::Empty()];
+        Grid { num_rows: 0, num_cols: 0, elements, grid: Vec::new() }
+    }
+
+    /// Like `read_lines`, but pads short rows with `.` to the longest, returning each row's own length
+    fn read_lines_padded(&mut self, lines: &[String]) -> Vec<usize> {
+        let row_lengths: Vec<usize> = lines.iter().map(|line| line.chars().count()).collect();
+        let width = row_lengths.iter().copied().max().unwrap_or(0);
+        self.read_lines(lines.iter().zip(&row_lengths).map(|(line, len)| format!("{line}{}", ".".repeat(width - len))))
+            .expect("padded rows are all the same length");
+        row_lengths
+    }
+
+    /// Fails on the first row whose length differs from the first row's
+    fn read_lines(& mut self, lines: impl Iterator<Item=String>) -> Result<(), SchematicError> {
+        for (rownum, line) in lines.enumerate() {
+            self.num_rows += 1;
+            let mut len = 0usize;
+            let mut current_num:Option<(Position, u32)> = None;
+            for (colnum, ch) in line.chars().enumerate() {
+                len += 1;
+                if let Some(num) = ch.to_digit(10) {
+                    if let Some((pos, prev_num)) = current_num {
+                        let new_pos = Position { line: rownum, cols: (pos.cols.0, colnum) };
+                        current_num = Some((new_pos, prev_num*10 + num));
+                    } else {
+                        let pos = Position { line:rownum, cols:(colnum, colnum)};
+                        current_num = Some((pos, num));
+                    }
+                    self.grid.push(self.elements.len())
+                } else {
+                    if let Some((pos, num)) = current_num {
+                        self.elements.push(Element::Number{pos, num});
+                        current_num = None;
+                    }
+                    
+                    if ch == '.' {
+                        self.grid.push(0);
+                    } else {
+                        self.grid.push(self.elements.len());
+                        let pos = Position {line:rownum, cols:(colnum, colnum)};
+                        self.elements.push(Element::Symbol{pos, ch});
+                    }
+                }
+                
+            }
+            if let Some((pos, num)) = current_num {
+                self.elements.push(Element::Number{pos, num});
+            }
+            
+            if self.num_cols == 0 {
+                self.num_cols = len;
+            } else if self.num_cols != len {
+                return Err(SchematicError::UnevenRow { line: rownum + 1, expected: self.num_cols, found: len });
+            }
+        }
+        Ok(())
+    }
+
+    /// The numbers and symbols with the links between those that touch
+    fn schematic(&self) -> Schematic {
+        let mut schematic = Schematic { numbers: Vec::new(), symbols: Vec::new(), number_links: Vec::new(), symbol_links: Vec::new() };
+        // where each element ended up in the schematic's own lists
+        let mut symbol_index: Vec<Option<usize>> = vec![None; self.elements.len()];
+        for (idx, element) in self.elements.iter().enumerate() {
+            match element {
+                Element::Number { pos, num } => schematic.numbers.push((*pos, *num)),
+                Element::Symbol { pos, ch } => {
+                    symbol_index[idx] = Some(schematic.symbols.len());
+                    schematic.symbols.push((*pos, *ch));
+                },
+                Element::Empty() => ()
+            }
+        }
+
+        schematic.symbol_links = vec![Vec::new(); schematic.symbols.len()];
+        for (number, (pos, _)) in schematic.numbers.iter().enumerate() {
+            let mut links: Vec<usize> = pos.iter_adjacent()
+                .filter(|(row, col)| *row < self.num_rows && *col < self.num_cols)
+                .filter_map(|(row, col)| symbol_index[self.grid[row*self.num_cols + col]])
+                .collect();
+            links.sort_unstable();
+            for &symbol in &links {
+                schematic.symbol_links[symbol].push(number);
+            }
+            schematic.number_links.push(links);
+        }
+        schematic
+    }
+}
+
+
+
+/// Numbers and symbols as the two sides of a graph, joined wherever a symbol touches a number.
+/// Both lists are in reading order.
+struct Schematic {
+    numbers: Vec<(Position, u32)>,
+    symbols: Vec<(Position, char)>,
+    /// For each number, the symbols next to it
+    number_links: Vec<Vec<usize>>,
+    /// For each symbol, the numbers next to it
+    symbol_links: Vec<Vec<usize>>
+}
+
+impl Schematic {
+    fn symbols_near(&self, number: usize) -> impl Iterator<Item = (Position, char)> + '_ {
+        self.number_links[number].iter().map(|&symbol| self.symbols[symbol])
+    }
+
+    fn numbers_near(&self, symbol: usize) -> impl Iterator<Item = (Position, u32)> + '_ {
+        self.symbol_links[symbol].iter().map(|&number| self.numbers[number])
+    }
+
+    /// Numbers next to at least one symbol
+    fn part_numbers(&self) -> Vec<u32> {
+        (0..self.numbers.len())
+            .filter(|&number| self.symbols_near(number).next().is_some())
+            .map(|number| self.numbers[number].1)
+            .collect()
+    }
+
+    /// One value for each symbol the rule picks out
+    fn apply(&self, rule: &SymbolRule) -> Vec<u64> {
+        (0..self.symbols.len())
+            .filter_map(|symbol| {
+                let nums: Vec<u32> = self.numbers_near(symbol).map(|(_, num)| num).collect();
+                rule.value(self.symbols[symbol].1, &nums)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum NumberCount {
+    Exactly(usize),
+    AtLeast(usize)
+}
+
+impl NumberCount {
+    fn matches(&self, count: usize) -> bool {
+        match self {
+            NumberCount::Exactly(n) => count == *n,
+            NumberCount::AtLeast(n) => count >= *n
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Combine {
+    Sum,
+    Product
+}
+
+/// Which symbols count and how their numbers combine, written `<symbol>:<count>:<sum|product>`.
+/// The symbol may be `any`, and the count may end in `+` for "at least", so gears are `*:2:product`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct SymbolRule {
+    symbol: Option<char>,
+    count: NumberCount,
+    combine: Combine
+}
+
+impl SymbolRule {
+    fn parse(text: &str) -> Result<Self, String> {
+        // the symbol itself may be a ':', so take it before splitting
+        let (symbol, rest) = match text.strip_prefix("any:") {
+            Some(rest) => (None, rest),
+            None => {
+                let mut chars = text.chars();
+                let symbol = chars.next().ok_or_else(|| String::from("Empty symbol rule"))?;
+                let rest = chars.as_str().strip_prefix(':').ok_or_else(|| format!("Bad symbol rule {text:?}"))?;
+                (Some(symbol), rest)
+            }
+        };
+        let (count, combine) = rest.split_once(':').ok_or_else(|| format!("Bad symbol rule {text:?}"))?;
+        let bad_count = |_| format!("Bad number count {count:?} in rule {text:?}");
+        let count = match count.strip_suffix('+') {
+            Some(n) => NumberCount::AtLeast(n.parse().map_err(bad_count)?),
+            None => NumberCount::Exactly(count.parse().map_err(bad_count)?)
+        };
+        let combine = match combine {
+            "sum" => Combine::Sum,
+            "product" => Combine::Product,
+            _ => return Err(format!("Unknown combination {combine:?} in rule {text:?}, expected sum or product"))
+        };
+        Ok(Self { symbol, count, combine })
+    }
+
+    /// What the rule makes of a symbol with these numbers next to it, if the rule covers it
+    fn value(&self, ch: char, nums: &[u32]) -> Option<u64> {
+        if self.symbol.is_some_and(|symbol| symbol != ch) || !self.count.matches(nums.len()) {
+            return None;
+        }
+        let nums = nums.iter().map(|num| u64::from(*num));
+        Some(match self.combine {
+            Combine::Sum => nums.sum(),
+            Combine::Product => nums.product()
+        })
+    }
+
+    /// Rules separated by spaces, as given to `--puzzle-rules`
+    fn parse_list(text: &str) -> Result<Vec<Self>, String> {
+        text.split_whitespace().map(Self::parse).collect()
+    }
+}
+
+/// Writes the schematic graph as Graphviz DOT, or as JSON for any other extension
+fn write_export(schematic: &Schematic, row_lengths: &[usize], path: &Path) -> std::io::Result<()> {
+    let text = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("dot") => schematic_dot(schematic),
+        _ => schematic_json(schematic, row_lengths)
+    };
+    fs::write(path, text)
+}
+
+/// An undirected graph with each node pinned at its column and row, for `neato -n` or `fdp`.
+/// Numbers with no symbol next to them are drawn in red.
+fn schematic_dot(schematic: &Schematic) -> String {
+    let mut out = String::from("graph schematic {\n    node [shape=box];\n");
+    for (i, (pos, num)) in schematic.numbers.iter().enumerate() {
+        let color = if schematic.number_links[i].is_empty() { ", color=red" } else { "" };
+        let x = (pos.cols.0 + pos.cols.1) as f64 / 2.0;
+        out.push_str(&format!("    n{i} [label=\"{num}\", pos=\"{x},{}!\"{color}];\n", -(pos.line as i64)));
+    }
+    for (i, (pos, ch)) in schematic.symbols.iter().enumerate() {
+        let label = ch.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+        out.push_str(&format!("    s{i} [label=\"{label}\", shape=circle, pos=\"{},{}!\"];\n", pos.cols.0, -(pos.line as i64)));
+    }
+    for (i, links) in schematic.number_links.iter().enumerate() {
+        for symbol in links {
+            out.push_str(&format!("    n{i} -- s{symbol};\n"));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Numbers and symbols with their positions (lines and columns from 0) and links,
+/// plus the length of every row that differs from the first
+fn schematic_json(schematic: &Schematic, row_lengths: &[usize]) -> String {
+    let ids = |prefix: char, links: &[usize]| {
+        links.iter().map(|i| format!("\"{prefix}{i}\"")).collect::<Vec<String>>().join(", ")
+    };
+    let numbers: Vec<String> = schematic.numbers.iter().enumerate().map(|(i, (pos, num))| format!(
+        "    {{\"id\": \"n{i}\", \"value\": {num}, \"line\": {}, \"cols\": [{}, {}], \"symbols\": [{}]}}",
+        pos.line, pos.cols.0, pos.cols.1, ids('s', &schematic.number_links[i])
+    )).collect();
+    let symbols: Vec<String> = schematic.symbols.iter().enumerate().map(|(i, (pos, ch))| format!(
+        "    {{\"id\": \"s{i}\", \"symbol\": {}, \"line\": {}, \"col\": {}, \"numbers\": [{}]}}",
+        crate::json_string(&ch.to_string()), pos.line, pos.cols.0, ids('n', &schematic.symbol_links[i])
+    )).collect();
+    let expected = row_lengths.first().copied().unwrap_or(0);
+    let uneven: Vec<String> = row_lengths.iter().enumerate()
+        .filter(|(_, len)| **len != expected)
+        .map(|(line, len)| format!("{{\"line\": {line}, \"length\": {len}}}"))
+        .collect();
+    format!(
+        "{{\n  \"width\": {expected},\n  \"rows\": {},\n  \"uneven_rows\": [{}],\n  \"numbers\": [\n{}\n  ],\n  \"symbols\": [\n{}\n  ]\n}}\n",
+        row_lengths.len(), uneven.join(", "), numbers.join(",\n"), symbols.join(",\n")
+    )
+}
+
+/// A problem with the shape of a schematic
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SchematicError {
+    /// A row (numbered from 1) whose length differs from the first row's
+    UnevenRow { line: usize, expected: usize, found: usize }
+}
+
+impl std::fmt::Display for SchematicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchematicError::UnevenRow { line, expected, found } =>
+                write!(f, "Line {line} is {found} characters long, but the schematic is {expected} wide")
+        }
+    }
+}
+
+/// Something learned once a row and both its neighbours have been read
+#[derive(Clone, PartialEq, Debug)]
+enum Finding {
+    /// A number next to at least one symbol
+    Part(u32),
+    /// Every symbol, with the numbers next to it in reading order
+    Symbol { pos: Position, ch: char, numbers: Vec<u32> }
+}
+
+/// A row reduced to its numbers and symbols, which is all the window keeps
+struct Row {
+    line: usize,
+    numbers: Vec<(Position, u32)>,
+    symbols: Vec<(usize, char)>
+}
+
+impl Row {
+    fn parse(line: usize, text: &str) -> Self {
+        let mut row = Row { line, numbers: Vec::new(), symbols: Vec::new() };
+        let mut current_num: Option<(Position, u32)> = None;
+        for (col, ch) in text.chars().enumerate() {
+            if let Some(digit) = ch.to_digit(10) {
+                current_num = Some(match current_num {
+                    Some((pos, num)) => (Position { line, cols: (pos.cols.0, col) }, num*10 + digit),
+                    None => (Position { line, cols: (col, col) }, digit)
+                });
+                continue;
+            }
+            row.numbers.extend(current_num.take());
+            if ch != '.' {
+                row.symbols.push((col, ch));
+            }
+        }
+        row.numbers.extend(current_num);
+        row
+    }
+}
+
+fn touches(pos: &Position, line: usize, col: usize) -> bool {
+    pos.line.abs_diff(line) <= 1 && col + 1 >= pos.cols.0 && col <= pos.cols.1 + 1
+}
+
+/// Reports the findings for `window[target]`, whose neighbours are also in the window
+fn report_row(window: &VecDeque<Row>, target: usize, found: &mut impl FnMut(Finding)) {
+    let row = &window[target];
+    let neighbours = || window.iter().filter(|other| other.line.abs_diff(row.line) <= 1);
+    for (pos, num) in &row.numbers {
+        if neighbours().any(|other| other.symbols.iter().any(|(col, _)| touches(pos, other.line, *col))) {
+            found(Finding::Part(*num));
+        }
+    }
+    for &(col, ch) in &row.symbols {
+        let numbers = neighbours()
+            .flat_map(|other| other.numbers.iter())
+            .filter(|(pos, _)| touches(pos, row.line, col))
+            .map(|(_, num)| *num)
+            .collect();
+        found(Finding::Symbol { pos: Position { line: row.line, cols: (col, col) }, ch, numbers });
+    }
+}
+
+/// Reads a schematic one row at a time, keeping no more than three rows.
+/// Each row's findings are passed to `found` as soon as the row after it has been read.
+fn stream_schematic(lines: impl Iterator<Item = String>, mut found: impl FnMut(Finding)) -> Result<(), SchematicError> {
+    let mut window: VecDeque<Row> = VecDeque::with_capacity(3);
+    let mut width = None;
+    for (line, text) in lines.enumerate() {
+        let len = text.chars().count();
+        match width {
+            None => width = Some(len),
+            Some(expected) if expected != len => return Err(SchematicError::UnevenRow { line: line + 1, expected, found: len }),
+            Some(_) => ()
+        }
+        window.push_back(Row::parse(line, &text));
+        if window.len() >= 2 {
+            report_row(&window, window.len() - 2, &mut found);
+        }
+        if window.len() == 3 {
+            window.pop_front();
+        }
+    }
+    if !window.is_empty() {
+        report_row(&window, window.len() - 1, &mut found);
+    }
+    Ok(())
+}
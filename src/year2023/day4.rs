@@ -0,0 +1,391 @@
+use crate::{options, trace, utilities};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn part1_test() {
+        let result = part1("./input/2023/day4_test1.txt");
+        assert_eq!(result, "The points total is 13");
+    }
+
+    #[test]
+    fn part2_test() {
+        let result = part2("./input/2023/day4_test1.txt");
+        assert_eq!(result, "The final total of cards is 30");
+    }
+
+    #[test]
+    fn cascade_counts_and_tree() {
+        let cards = read_cards("./input/2023/day4_test1.txt");
+        let matches: Vec<usize> = cards.iter().map(|c| c.winning_count() as usize).collect();
+        let cascade = Cascade::play(&matches, OverflowPolicy::Error).unwrap();
+        assert_eq!(cascade.counts, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.children[0], vec![1, 2, 3, 4]);
+        assert_eq!(cards[0].matched_numbers(), vec![48, 83, 86, 17]);
+
+        let tree = cascade_tree(&cascade, &cards);
+        assert!(tree.starts_with("Card 1 (4 matches)\n  Card 2 (2 matches)\n    Card 3 (2 matches)\n      Card 4 (1 match)\n        Card 5 (0 matches)\n      Card 5 (see above)\n"));
+        assert!(tree.ends_with("Card 6 (0 matches)"));
+    }
+
+    #[test]
+    fn any_id_and_groups() {
+        let card = Card::read_line("Ticket   A-7: 100 2000 100 5 | 5 70000 | 2000 9 9", 0);
+        assert_eq!(card.id, "Ticket A-7");
+        assert!(matches!(card.nums, NumberSet::Sorted(_)));
+        assert_eq!(card.matched_numbers(), vec![2000, 5]);
+        assert_eq!(card.duplicates, vec![100, 9]);
+        assert_eq!(card.value(), 2);
+
+        let unnamed = Card::read_line("1 2 3 | 3 2", 4);
+        assert_eq!(unnamed.id, "Card 5");
+        assert!(matches!(unnamed.nums, NumberSet::Bits(_)));
+        assert_eq!(unnamed.winning_count(), 2);
+    }
+
+    #[test]
+    fn thousands_of_numbers() {
+        let winning: Vec<String> = (0..20_000).map(|n| (n % 15_000).to_string()).collect();
+        let held: Vec<String> = (10_000..30_000).rev().map(|n| n.to_string()).collect();
+        let card = Card::read_line(&format!("Card 1: {} | {}", winning.join(" "), held.join(" ")), 0);
+        assert_eq!(card.winning.len(), 15_000);
+        assert_eq!(card.winning_count(), 5_000);
+        assert_eq!(card.duplicates, (0..5_000).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn bitset_and_sorted_sets_agree() {
+        let mut rng = crate::rng::Rng::new(4);
+        for limit in [100, 1 << 20] {
+            let held: Vec<u64> = (0..3000).map(|_| rng.below(limit)).collect();
+            let set = NumberSet::new(&held);
+            for _ in 0..3000 {
+                let num = rng.below(limit);
+                assert_eq!(set.contains(num), held.contains(&num));
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_policies() {
+        let matches = [2, 0, 1];
+        assert_eq!(Cascade::play(&matches, OverflowPolicy::Clamp).unwrap().counts, vec![1, 2, 2]);
+        assert_eq!(Cascade::play(&matches, OverflowPolicy::Wrap).unwrap().counts, vec![3, 2, 2]);
+        assert_eq!(
+            Cascade::play(&matches, OverflowPolicy::Error).unwrap_err(),
+            CascadeError::PastLastCard { card: 3, matches: 1, cards: 3 }
+        );
+        assert_eq!("wrap".parse(), Ok(OverflowPolicy::Wrap));
+    }
+
+    #[test]
+    fn wrapping_more_than_a_lap() {
+        // card 1 wins card 2, itself, then card 2 again; its own new copy comes too late to win anything
+        let cascade = Cascade::play(&[3, 0], OverflowPolicy::Wrap).unwrap();
+        assert_eq!(cascade.counts, vec![2, 3]);
+        assert_eq!(cascade.total(), 5);
+        assert_eq!(cascade.children[0], vec![1, 0, 1]);
+        // card 2's two copies both win card 3 twice over
+        assert_eq!(Cascade::play(&[1, 4, 0], OverflowPolicy::Wrap).unwrap().counts, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn counts_beyond_u64() {
+        // every card wins all the cards after it, so card n ends up with 2^(n-1) copies
+        let matches: Vec<usize> = (0..100).rev().collect();
+        let cascade = Cascade::play(&matches, OverflowPolicy::Error).unwrap();
+        assert_eq!(cascade.counts[99], 1u128 << 99);
+        assert_eq!(cascade.total(), (1u128 << 100) - 1);
+    }
+}
+
+pub fn part1(path: &str) -> String {
+    let cards = read_cards(path);
+
+    let total:u128 = cards
+        .iter()
+        .map(|c| c.value())
+        .sum();
+    warn_duplicates(&cards);
+
+    format!("The points total is {total}")
+}
+
+pub fn part2(path: &str) -> String {
+    let cards = read_cards(path);
+    warn_duplicates(&cards);
+    let matches: Vec<usize> = cards.iter().map(|c| c.winning_count() as usize).collect();
+    let policy: OverflowPolicy = options::get_or("overflow", OverflowPolicy::Clamp);
+    let cascade = Cascade::play(&matches, policy).unwrap_or_else(|err| panic!("{err}"));
+    let total = cascade.total();
+
+    match options::get("breakdown").as_deref() {
+        None => format!("The final total of cards is {total}"),
+        Some("table") => format!("{}\nThe final total of cards is {total}", cascade_table(&cascade, &cards)),
+        Some("tree") => format!("{}\nThe final total of cards is {total}", cascade_tree(&cascade, &cards)),
+        Some(other) => panic!("Unknown breakdown {other:?}, expected table or tree")
+    }
+}
+
+fn read_cards(path: &str) -> Vec<Card> {
+    let lines = utilities::string_iterator(path);
+    let mut cards: Vec<Card> = Vec::new();
+    for (position, line) in lines.enumerate() {
+        cards.push(Card::read_line(&line, position));
+    }
+    cards
+}
+
+/// Cards that repeat a number are probably mistyped, so name them at the `info` trace level
+fn warn_duplicates(cards: &[Card]) {
+    let repeats: Vec<String> = cards
+        .iter()
+        .filter(|card| !card.duplicates.is_empty())
+        .map(|card| {
+            let nums: Vec<String> = card.duplicates.iter().map(|n| n.to_string()).collect();
+            format!("{} ({})", card.id, nums.join(" "))
+        })
+        .collect();
+    if !repeats.is_empty() {
+        trace::info(|| format!("{} cards repeat numbers: {}", repeats.len(), repeats.join(", ")));
+    }
+}
+
+/// What happens to copies won past the last card
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum OverflowPolicy {
+    /// They are lost
+    Clamp,
+    /// They go round to the first cards again, lap after lap, so a card can be won several times over.
+    /// Copies of the winning card itself or of cards before it arrive after those were scratched and win nothing more;
+    /// cards after it are scratched later with every copy they got.
+    Wrap,
+    /// The cascade stops with an error
+    Error
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clamp" => Ok(Self::Clamp),
+            "wrap" => Ok(Self::Wrap),
+            "error" => Ok(Self::Error),
+            _ => Err(format!("unknown overflow policy {s:?}, expected clamp, wrap or error"))
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CascadeError {
+    /// Card `card` (numbered from 1) matched more numbers than there are cards after it
+    PastLastCard { card: usize, matches: usize, cards: usize }
+}
+
+impl Display for CascadeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CascadeError::PastLastCard { card, matches, cards } =>
+                write!(f, "Card {card} wins copies of the next {matches} cards, but there are only {cards} cards")
+        }
+    }
+}
+
+/// Every card scratched in order, with its copies
+#[derive(Debug)]
+struct Cascade {
+    /// Copies of each card held at the end, the original included
+    counts: Vec<u128>,
+    /// The cards each card wins a copy of
+    children: Vec<Vec<usize>>
+}
+
+impl Cascade {
+    /// Plays the cards given how many numbers each one matched
+    fn play(matches: &[usize], policy: OverflowPolicy) -> Result<Self, CascadeError> {
+        let cards = matches.len();
+        let mut counts: Vec<u128> = vec![1; cards];
+        let mut children: Vec<Vec<usize>> = Vec::with_capacity(cards);
+        for (i, &won) in matches.iter().enumerate() {
+            let past_end = i + won >= cards;
+            let won_cards: Vec<usize> = match policy {
+                OverflowPolicy::Error if past_end => return Err(CascadeError::PastLastCard { card: i + 1, matches: won, cards }),
+                OverflowPolicy::Wrap => (i + 1..=i + won).map(|j| j % cards).collect(),
+                _ => (i + 1..(i + 1 + won).min(cards)).collect()
+            };
+            // read first, since wrapping can hand this card copies of itself
+            let copies = counts[i];
+            for &j in &won_cards {
+                counts[j] += copies;
+            }
+            children.push(won_cards);
+        }
+        Ok(Self { counts, children })
+    }
+
+    fn total(&self) -> u128 {
+        self.counts.iter().sum()
+    }
+}
+
+/// One row per card: its matches, the numbers that matched, its final copies, any repeated numbers
+/// and the cards it wins
+fn cascade_table(cascade: &Cascade, cards: &[Card]) -> String {
+    let id_width = cards.iter().map(|card| card.id.len()).max().unwrap_or(0).max(4);
+    let numbers = |nums: &[u64]| nums.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+    let mut out = format!("{:<id_width$}  {:>7}  {:>20}  {:<30}  {:<12}  wins\n", "card", "matches", "copies", "matched numbers", "repeated");
+    for (i, card) in cards.iter().enumerate() {
+        let matched = card.matched_numbers();
+        let wins: Vec<&str> = cascade.children[i].iter().map(|&j| cards[j].id.as_str()).collect();
+        let row = format!(
+            "{:<id_width$}  {:>7}  {:>20}  {:<30}  {:<12}  {}",
+            card.id, matched.len(), cascade.counts[i], numbers(&matched), numbers(&card.duplicates), wins.join(", ")
+        );
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out.pop();
+    out
+}
+
+/// The cards each card wins, indented under it, starting from the cards nothing wins.
+/// Each card's own wins are only spelled out the first time it appears.
+fn cascade_tree(cascade: &Cascade, cards: &[Card]) -> String {
+    fn visit(card: usize, depth: usize, cascade: &Cascade, cards: &[Card], shown: &mut [bool], out: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        if shown[card] {
+            out.push(format!("{indent}{} (see above)", cards[card].id));
+            return;
+        }
+        shown[card] = true;
+        let matches = cards[card].winning_count();
+        let plural = if matches == 1 { "match" } else { "matches" };
+        out.push(format!("{indent}{} ({matches} {plural})", cards[card].id));
+        for &child in &cascade.children[card] {
+            visit(child, depth + 1, cascade, cards, shown, out);
+        }
+    }
+
+    let mut won = vec![false; cards.len()];
+    for &child in cascade.children.iter().flatten() {
+        won[child] = true;
+    }
+    let mut shown = vec![false; cards.len()];
+    let mut out = Vec::new();
+    for root in 0..cards.len() {
+        if !won[root] || !shown[root] {
+            visit(root, 0, cascade, cards, &mut shown, &mut out);
+        }
+    }
+    out.join("\n")
+}
+
+/// The numbers held on a card: a bitset when they are all small, otherwise a sorted list searched by halves
+#[derive(Debug)]
+enum NumberSet {
+    Bits(Vec<u64>),
+    Sorted(Vec<u64>)
+}
+
+impl NumberSet {
+    /// Above this a bitset would be mostly empty words
+    const MAX_BIT: u64 = 1 << 16;
+
+    fn new(nums: &[u64]) -> Self {
+        match nums.iter().max() {
+            Some(&max) if max >= Self::MAX_BIT => {
+                let mut sorted = nums.to_vec();
+                sorted.sort_unstable();
+                sorted.dedup();
+                NumberSet::Sorted(sorted)
+            },
+            max => {
+                let mut bits = vec![0u64; max.map_or(0, |max| *max as usize / 64 + 1)];
+                for &num in nums {
+                    bits[num as usize / 64] |= 1 << (num % 64);
+                }
+                NumberSet::Bits(bits)
+            }
+        }
+    }
+
+    fn contains(&self, num: u64) -> bool {
+        match self {
+            NumberSet::Bits(bits) => bits.get(num as usize / 64).is_some_and(|word| word >> (num % 64) & 1 == 1),
+            NumberSet::Sorted(sorted) => sorted.binary_search(&num).is_ok()
+        }
+    }
+}
+
+/// Numbers that appear more than once in `nums`, each listed once in order of their second appearance
+fn repeated(nums: &[u64]) -> Vec<u64> {
+    let mut seen = HashSet::with_capacity(nums.len());
+    let mut reported = HashSet::new();
+    nums.iter().copied().filter(|&num| !seen.insert(num) && reported.insert(num)).collect()
+}
+
+#[derive(Debug)]
+struct Card {
+    /// Whatever comes before the colon, with runs of spaces squeezed
+    id: String,
+    /// The first group of numbers, without repeats
+    winning: Vec<u64>,
+    /// Every later group together
+    nums: NumberSet,
+    /// Numbers listed twice among the winning numbers, or twice among the rest
+    duplicates: Vec<u64>
+}
+
+impl Card {
+    /// Reads `<id>: <winning> | <numbers> [| <more numbers> ...]`. The ID can be anything without a colon;
+    /// with no colon at all the card is named by its position, numbered from 1.
+    fn read_line(line:&str, position: usize) -> Self {
+        let (id, card_text) = match line.split_once(':') {
+            Some((id, card_text)) => (id.split_whitespace().collect::<Vec<&str>>().join(" "), card_text),
+            None => (format!("Card {}", position + 1), line)
+        };
+        let parse_group = |group: &str| -> Vec<u64> {
+            group
+                .split_whitespace()
+                .map(|n| n.parse::<u64>().unwrap_or_else(|_| panic!("Couldn't parse number {n:?} on {id}")))
+                .collect()
+        };
+
+        let mut groups = card_text.split('|');
+        let listed_winning = parse_group(groups.next().expect("Couldn't extract winning numbers part"));
+        let held: Vec<u64> = groups.flat_map(parse_group).collect();
+        if held.is_empty() && !card_text.contains('|') {
+            panic!("Couldn't extract numbers part of {id}");
+        }
+
+        let mut duplicates = repeated(&listed_winning);
+        let mut listed: HashSet<u64> = duplicates.iter().copied().collect();
+        duplicates.extend(repeated(&held).into_iter().filter(|&num| listed.insert(num)));
+        let mut seen = HashSet::with_capacity(listed_winning.len());
+        let winning: Vec<u64> = listed_winning.into_iter().filter(|&num| seen.insert(num)).collect();
+
+        Self { id, winning, nums: NumberSet::new(&held), duplicates }
+    }
+
+    fn value(&self) -> u128 {
+        match self.winning_count() {
+            0 => 0,
+            count => 1u128.checked_shl(count - 1).unwrap_or_else(|| panic!("{} is worth more than 2^127 points", self.id))
+        }
+    }
+
+    fn winning_count(&self) -> u32 {
+        self.winning.iter().filter(|&&n| self.nums.contains(n)).count() as u32
+    }
+
+    /// The winning numbers this card holds, in the order they are listed as winning
+    fn matched_numbers(&self) -> Vec<u64> {
+        self.winning.iter().filter(|&&n| self.nums.contains(n)).copied().collect()
+    }
+}
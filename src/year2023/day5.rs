@@ -5,24 +5,111 @@ use std::ops::Range;
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::property::{self, Arbitrary};
+    use crate::rng::Rng;
     
     #[test]
     fn part1_test() {
-        let output = part1("./input/day5_test1.txt");
+        let output = part1("./input/2023/day5_test1.txt");
         assert_eq!(output, "The minimum location number is 35");
     }
 
     #[test]
     fn part2_test() {
-        let output = part2("./input/day5_test1.txt");
+        let output = part2("./input/2023/day5_test1.txt");
         assert_eq!(output, "The minimum location number is 46");  
     }
 
     #[test]
     fn part2_naive_test() {
-        let output = _part2("./input/day5_test1.txt");
+        let output = _part2("./input/2023/day5_test1.txt");
         assert_eq!(output, "The minimum location number is 46");  
     }
+
+    /// Seed ranges plus maps that each shuffle contiguous blocks of `0..limit`,
+    /// so every map can be run backwards the way `part2` does
+    #[derive(Clone, Debug)]
+    struct Almanac {
+        seeds: Vec<(usize, usize)>,
+        maps: Vec<Vec<(usize, usize, usize)>>
+    }
+
+    impl Almanac {
+        fn text(&self) -> String {
+            let seeds: Vec<String> = self.seeds.iter().map(| (start, len) | format!("{start} {len}")).collect();
+            let mut out = format!("seeds: {}\n", seeds.join(" "));
+            for rules in &self.maps {
+                out.push_str("\nfrom-to-to map:\n");
+                for (dest, src, len) in rules {
+                    out.push_str(&format!("{dest} {src} {len}\n"));
+                }
+            }
+            out
+        }
+    }
+
+    impl Arbitrary for Almanac {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            let limit = 20 + 10 * size;
+            let seeds = (0..rng.range(1..4))
+                .map(| _ | {
+                    let start = rng.range(0..limit - 1);
+                    (start, rng.range(1..limit - start))
+                })
+                .collect();
+            let maps = (0..rng.range(1..8))
+                .map(| _ | {
+                    let mut cuts: Vec<usize> = (0..rng.range(1..6)).map(| _ | rng.range(1..limit)).collect();
+                    cuts.push(0);
+                    cuts.push(limit);
+                    cuts.sort();
+                    cuts.dedup();
+                    let mut segments: Vec<(usize, usize)> = cuts.windows(2).map(| w | (w[0], w[1] - w[0])).collect();
+                    rng.shuffle(&mut segments);
+                    let mut dest = 0;
+                    segments.into_iter()
+                        .map(| (src, len) | {
+                            dest += len;
+                            (dest - len, src, len)
+                        })
+                        .collect()
+                })
+                .collect();
+            Self { seeds, maps }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            for i in 0..self.seeds.len() {
+                if self.seeds.len() > 1 {
+                    let mut fewer = self.clone();
+                    fewer.seeds.remove(i);
+                    smaller.push(fewer);
+                }
+                if self.seeds[i].1 > 1 {
+                    let mut shorter = self.clone();
+                    shorter.seeds[i].1 /= 2;
+                    smaller.push(shorter);
+                }
+            }
+            for i in 0..self.maps.len() {
+                let mut fewer = self.clone();
+                fewer.maps.remove(i);
+                smaller.push(fewer);
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn naive_matches_reverse_search() {
+        let path = std::env::temp_dir().join("aoc2023_day5_property.txt");
+        let path = path.to_str().unwrap();
+        property::assert_property(5, 100, 20, | almanac: &Almanac | {
+            std::fs::write(path, almanac.text()).map_err(| e | e.to_string())?;
+            property::agree(&_part2(path), &part2(path))
+        });
+    }
 }
 
 pub fn part1(path: &str) -> String {
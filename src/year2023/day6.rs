@@ -6,13 +6,13 @@ mod testing {
     
     #[test]
     fn part1_test() {
-        let output = part1("./input/day6_test1.txt");
+        let output = part1("./input/2023/day6_test1.txt");
         assert_eq!(output, "The product is 288");
     }
 
     #[test]
     fn part2_test() {
-        let output = part2("./input/day6_test1.txt");
+        let output = part2("./input/2023/day6_test1.txt");
         assert_eq!(output, "There are 71503 ways to win");  
     }
 }
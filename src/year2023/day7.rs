@@ -7,14 +7,14 @@ mod testing {
 
     #[test]
     fn part1_test() {
-        let result = part1("./input/day7_test1.txt");
+        let result = part1("./input/2023/day7_test1.txt");
 
         assert_eq!(result, "The total winnings are 6440");
     }
 
     #[test]
     fn part2_test() {
-        let result = part2("./input/day7_test1.txt");
+        let result = part2("./input/2023/day7_test1.txt");
 
         assert_eq!(result, "The total winnings are 5905");
     }
@@ -7,21 +7,21 @@ mod testing {
 
     #[test]
     fn part1_test1() {
-        let result = part1("./input/day8_test1.txt");
+        let result = part1("./input/2023/day8_test1.txt");
 
         assert_eq!(result, "Path requires 2 steps");
     }
 
     #[test]
     fn part1_test2() {
-        let result = part1("./input/day8_test2.txt");
+        let result = part1("./input/2023/day8_test2.txt");
 
         assert_eq!(result, "Path requires 6 steps");
     }
 
     #[test]
     fn part2_test() {
-        let result = part2("./input/day8_test3.txt");
+        let result = part2("./input/2023/day8_test3.txt");
 
         assert_eq!(result, "Path requires 6 steps");
     }
@@ -6,14 +6,14 @@ mod testing {
 
     #[test]
     fn part1_test() {
-        let result = part1("./input/day9_test1.txt");
+        let result = part1("./input/2023/day9_test1.txt");
 
         assert_eq!(result, "The sum of forecast values is 114");
     }
 
     #[test]
     fn part2_test() {
-        let result = part2("./input/day9_test1.txt");
+        let result = part2("./input/2023/day9_test1.txt");
 
         assert_eq!(result, "The sum of backcast values is 2");
     }